@@ -1,3 +1,4 @@
 fn main() {
+    #[cfg(feature = "gui")]
     slint_build::compile("src/ui/timer.slint").unwrap();
-}
\ No newline at end of file
+}
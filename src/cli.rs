@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Parser};
+use rust_slint_timer::timer::{parse_timer_input, validate_duration, TimerError, TimerInput};
+
+/// A desktop countdown timer, stopwatch and Pomodoro timer
+///
+/// Without arguments the window opens with the configured default duration,
+/// or the timer left running when it was last closed.
+#[derive(Debug, Parser)]
+#[command(name = "rust-slint-timer", version)]
+#[command(group(
+    ArgGroup::new("command")
+        .args(["export", "import_history", "import_presets"])
+        .conflicts_with_all(["duration", "preset", "label", "start", "overtime"])
+))]
+pub struct Cli {
+    /// Countdown to set, like the quick-entry field: "25m", "1h30m", "0:25:00" or "until 14:30"
+    #[arg(value_parser = timer_input)]
+    pub duration: Option<TimerInput>,
+    /// Set the countdown from a saved preset, labelled with its name
+    #[arg(long, value_name = "NAME", conflicts_with = "duration")]
+    pub preset: Option<String>,
+    /// What the run is for, saved with it in the session history
+    #[arg(long)]
+    pub label: Option<String>,
+    /// Start the timer as soon as the window opens
+    #[arg(long)]
+    pub start: bool,
+    /// Keep counting past zero when the countdown completes
+    #[arg(long)]
+    pub overtime: bool,
+    /// Read the configuration from PATH instead of ~/.config/rust-slint-timer/config.toml
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Export the session history to PATH (.csv, .json or .ics) and exit
+    #[arg(long, value_name = "PATH")]
+    pub export: Option<PathBuf>,
+    /// Add the sessions in PATH (.csv or .json) to the history and exit
+    #[arg(long, value_name = "PATH")]
+    pub import_history: Option<PathBuf>,
+    /// Add the presets in PATH (.csv or .json) to the saved presets and exit
+    #[arg(long, value_name = "PATH")]
+    pub import_presets: Option<PathBuf>,
+}
+
+/// Parses the duration argument, rejecting durations the timer cannot count down
+fn timer_input(text: &str) -> Result<TimerInput, TimerError> {
+    let input = parse_timer_input(text)?;
+    if let TimerInput::Duration(duration) = input {
+        validate_duration(duration)?;
+    }
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::error::ErrorKind;
+    use std::time::Duration;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("rust-slint-timer").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_timer_arguments() {
+        let cli = parse(&["25m", "--label", "Write report", "--start", "--overtime"]).unwrap();
+        assert_eq!(cli.duration, Some(TimerInput::Duration(Duration::from_secs(25 * 60))));
+        assert_eq!(cli.label.as_deref(), Some("Write report"));
+        assert!(cli.start && cli.overtime);
+
+        let cli = parse(&["--preset", "Tea", "--config", "timer.toml"]).unwrap();
+        assert_eq!(cli.duration, None);
+        assert_eq!(cli.preset.as_deref(), Some("Tea"));
+        assert_eq!(cli.config, Some(PathBuf::from("timer.toml")));
+        assert!(!cli.start);
+
+        assert!(matches!(parse(&["until 14:30"]).unwrap().duration, Some(TimerInput::Until(_))));
+    }
+
+    #[test]
+    fn test_invalid_durations_are_rejected() {
+        for duration in ["25x", "0", "100d", ""] {
+            let error = parse(&[duration]).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::ValueValidation, "{}", duration);
+            assert_ne!(error.exit_code(), 0);
+        }
+    }
+
+    #[test]
+    fn test_conflicting_arguments() {
+        assert_eq!(parse(&["25m", "--preset", "Tea"]).unwrap_err().kind(), ErrorKind::ArgumentConflict);
+        assert_eq!(parse(&["--export", "a.csv", "--start"]).unwrap_err().kind(), ErrorKind::ArgumentConflict);
+        assert_eq!(
+            parse(&["--export", "a.csv", "--import-history", "b.csv"]).unwrap_err().kind(),
+            ErrorKind::ArgumentConflict
+        );
+        assert_eq!(parse(&["--export", "a.csv"]).unwrap().export, Some(PathBuf::from("a.csv")));
+    }
+}
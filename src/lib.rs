@@ -1 +1,31 @@
-pub mod timer;
\ No newline at end of file
+//! Timer engine behind the Rust Slint Timer desktop application.
+//!
+//! The [`timer`] module is usable on its own, without the Slint GUI. Disable
+//! the default `gui` feature to depend on the engine from headless tools:
+//!
+//! ```toml
+//! rust-slint-timer = { version = "0.1", default-features = false }
+//! ```
+//!
+//! Enable the optional `serde` feature to serialize [`timer::TimerState`] and
+//! the configuration types, and to read and write them as versioned JSON with
+//! `to_json` and `from_json`.
+//!
+//! The `config` feature, enabled by `gui`, reads the application's
+//! `config.toml` with `Config` and `ConfigFile`.
+//!
+//! Enable the optional `sqlite` feature to keep the session history, presets
+//! and settings in a local SQLite database with `Database`, which can be
+//! queried by date range and label.
+//!
+//! ```
+//! use rust_slint_timer::timer::TimerLogic;
+//!
+//! let mut timer = TimerLogic::with_time(0, 25, 0).unwrap();
+//! assert_eq!(timer.get_remaining_time_string(), "00:25:00");
+//!
+//! timer.start_timer();
+//! assert!(timer.is_running());
+//! ```
+
+pub mod timer;
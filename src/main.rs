@@ -1,50 +1,341 @@
-mod timer;
+mod cli;
+mod store;
 
 use std::rc::Rc;
 use std::cell::RefCell;
 use slint::ComponentHandle;
-use timer::TimerLogic;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+use chrono::{Datelike, Local, NaiveDate, TimeDelta};
+use clap::Parser;
+use cli::Cli;
+use store::Store;
+use rust_slint_timer::timer::{
+    average_length, completion_rate, daily_totals, data_dir, export_history, from_json, import_presets,
+    import_sessions, parse_timer_input, streaks, to_json, validate_time_with_days, weekly_totals, Config,
+    ConfigFile, DisplayPrecision, ExportFormat, HookEvent, HookSettings, ImportFormat, PeriodTotal, Pomodoro,
+    PomodoroConfig, Preset, PresetList, Sequence, SequenceEvent, SequenceRunner, SessionOutcome,
+    SessionRecord, SoundSettings, StateStore, ThemeName, TimerError, TimerId, TimerInput, TimerLogic,
+    TimerManager, TimerMode,
+};
+use slint::{Model, ModelRc, VecModel};
 
 slint::include_modules!();
 
 fn main() -> Result<(), slint::PlatformError> {
+    // Invalid arguments, including durations the timer cannot count down,
+    // exit with a non-zero code before the window opens
+    let cli = Cli::parse();
+    
+    // `--export history.ics`, `--import-history history.csv` and
+    // `--import-presets presets.csv` work on the saved data without opening
+    // the window; the format follows the extension
+    if let Some(path) = &cli.export {
+        std::process::exit(export_command(path));
+    }
+    if let Some(path) = &cli.import_history {
+        std::process::exit(import_history_command(path));
+    }
+    if let Some(path) = &cli.import_presets {
+        std::process::exit(import_presets_command(path));
+    }
+    
+    // The countdown and label given on the command line, from `--preset` or the duration
+    let preset = match cli.preset.as_deref().map(load_preset) {
+        Some(Ok(preset)) => Some(preset),
+        Some(Err(error)) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        None => None,
+    };
+    let launch_input = preset.as_ref().map(|preset| TimerInput::Duration(preset.duration)).or(cli.duration);
+    let launch_label = cli.label.clone().or_else(|| preset.map(|preset| preset.name));
+    
+    // Read the configuration, keeping the defaults if the file is invalid.
+    // A file named with `--config` must exist and be valid
+    let mut config_file = match &cli.config {
+        Some(path) => Some(ConfigFile::new(path)),
+        None => ConfigFile::in_config_dir(),
+    };
+    let mut config = Config::default();
+    let mut config_error = None;
+    if let Some(config_file) = &mut config_file {
+        let loaded = match &cli.config {
+            Some(path) if !path.is_file() => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no such file")),
+            _ => config_file.load(),
+        };
+        match loaded {
+            Ok(loaded) => config = loaded,
+            Err(error) if cli.config.is_some() => {
+                eprintln!("Could not load configuration from {}: {}", config_file.path().display(), error);
+                std::process::exit(1);
+            }
+            Err(error) => config_error = Some(error),
+        }
+    }
+    
     let ui = TimerWindow::new()?;
+    if let (Some(config_file), Some(error)) = (&config_file, &config_error) {
+        show_config_error(&ui, config_file.path(), error);
+    }
+    apply_appearance(&ui, &config);
     
     // Create timer logic instance
     let timer_logic = Rc::new(RefCell::new(TimerLogic::new()));
     
-    // Set up initial UI state
-    ui.set_input_hours(0);
-    ui.set_input_minutes(5);
-    ui.set_input_seconds(0);
-    ui.set_display_time("00:05:00".into());
-    ui.set_is_running(false);
-    ui.set_is_completed(false);
+    // Pomodoro controller driving the timer while in Pomodoro mode
+    let pomodoro: Rc<RefCell<Option<Pomodoro>>> = Rc::new(RefCell::new(None));
+    
+    // Interval program runner driving the timer while in Intervals mode
+    let intervals: Rc<RefCell<Option<SequenceRunner>>> = Rc::new(RefCell::new(None));
+    
+    // Named timers running alongside the main timer, shown as a list
+    let timer_manager = Rc::new(RefCell::new(TimerManager::new()));
+    let named_timer_rows = Rc::new(VecModel::<TimerRow>::default());
+    ui.set_named_timers(ModelRc::from(named_timer_rows.clone()));
+    
+    // Session history, presets and settings, kept in the SQLite database
+    // with the `sqlite` feature and in flat files otherwise
+    let store = match Store::open() {
+        Ok(store) => Some(Rc::new(store)),
+        Err(error) => {
+            eprintln!("Could not open the saved history and presets: {}", error);
+            None
+        }
+    };
+    
+    // Saved presets, shown as a list that sets the main timer when clicked.
+    // If they cannot be read they are left alone rather than overwritten
+    let mut preset_store = store.clone();
+    let presets = Rc::new(RefCell::new(PresetList::new()));
+    let preset_rows = Rc::new(VecModel::<PresetRow>::default());
+    ui.set_presets(ModelRc::from(preset_rows.clone()));
+    if let Some(store) = &preset_store {
+        match store.presets() {
+            Ok(list) => *presets.borrow_mut() = list,
+            Err(error) => {
+                eprintln!("Could not load presets from {}: {}", store.presets_path().display(), error);
+                preset_store = None;
+            }
+        }
+    }
+    preset_rows.set_vec(presets.borrow().presets().iter().map(preset_row).collect::<Vec<_>>());
+    let preset_store = Rc::new(preset_store);
+    
+    // Set up initial UI state from the configured defaults
+    {
+        let mut timer = timer_logic.borrow_mut();
+        // The duration was validated when the configuration was read
+        let _ = timer.set_duration(config.timer.default_duration);
+        timer.set_overtime(config.timer.overtime);
+        let state = timer.get_state();
+        ui.set_input_days(state.days as i32);
+        ui.set_input_hours(state.hours as i32);
+        ui.set_input_minutes(state.minutes as i32);
+        ui.set_input_seconds(state.seconds as i32);
+        ui.set_display_time(timer.get_display_time_string().into());
+        ui.set_is_running(false);
+        ui.set_is_completed(false);
+        ui.set_overtime_enabled(config.timer.overtime);
+        ui.set_preset_auto_start(config.timer.auto_start);
+    }
+    if let Some(store) = &store {
+        load_settings(&ui, &mut timer_logic.borrow_mut(), store);
+    }
+    
+    // Restore the timer from the previous session, then save every state change
+    let mut restored = false;
+    if let Some(state_store) = StateStore::in_data_dir() {
+        match state_store.load() {
+            Ok(Some(state)) => {
+                let mut timer = timer_logic.borrow_mut();
+                timer.restore_state(state);
+                timer.tick();
+                show_restored_timer(&ui, &timer);
+                restored = true;
+            }
+            Ok(None) => {}
+            Err(error) => eprintln!("Could not restore timer from {}: {}", state_store.path().display(), error),
+        }
+        timer_logic.borrow_mut().set_state_callback(move |state| {
+            if let Err(error) = state_store.save(&state) {
+                eprintln!("Could not save timer state to {}: {}", state_store.path().display(), error);
+            }
+        });
+    }
+    
+    // Record every finished run in the session history. The session callback
+    // must be Send, so records are handed to the tick loop to save and show
+    let history_store = store.clone();
+    let history: Rc<RefCell<Vec<SessionRecord>>> = Rc::new(RefCell::new(Vec::new()));
+    let history_rows = Rc::new(VecModel::<HistoryRow>::default());
+    ui.set_history(ModelRc::from(history_rows.clone()));
+    if let Some(history_store) = &history_store {
+        match history_store.history() {
+            Ok(records) => {
+                history_rows.set_vec(records.iter().rev().map(history_row).collect::<Vec<_>>());
+                *history.borrow_mut() = records;
+            }
+            Err(error) => {
+                eprintln!("Could not load history from {}: {}", history_store.history_path().display(), error)
+            }
+        }
+    }
+    update_stats_ui(&ui, &history.borrow());
+    let (session_sender, session_receiver) = mpsc::channel::<SessionRecord>();
+    timer_logic.borrow_mut().set_session_callback(move |record| {
+        // The receiver lives as long as the tick loop, so this only fails on shutdown
+        let _ = session_sender.send(record);
+    });
+    
+    // Apply the timer given on the command line over the defaults and any
+    // restored timer. The configured auto-start applies to new timers only
+    {
+        let mut timer = timer_logic.borrow_mut();
+        if let Some(input) = launch_input {
+            if timer.mode() != TimerMode::Countdown {
+                timer.set_mode(TimerMode::Countdown);
+                ui.set_timer_mode(0);
+            }
+            let result = match input {
+                TimerInput::Duration(duration) => timer.set_duration(duration),
+                TimerInput::Until(time) => timer.set_target_time(time),
+            };
+            if let Err(error) = result {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+            let state = timer.get_state();
+            ui.set_input_days(state.days as i32);
+            ui.set_input_hours(state.hours as i32);
+            ui.set_input_minutes(state.minutes as i32);
+            ui.set_input_seconds(state.seconds as i32);
+        }
+        if let Some(label) = launch_label {
+            timer.set_label(Some(label.clone()));
+            ui.set_session_label(label.into());
+        }
+        if cli.overtime {
+            timer.set_overtime(true);
+            ui.set_overtime_enabled(true);
+        }
+        let fresh = !restored || launch_input.is_some();
+        if cli.start || (config.timer.auto_start && fresh) {
+            timer.start_timer();
+            run_hook(&config.hooks, HookEvent::Start, timer.label());
+        }
+        
+        ui.set_display_time(timer.get_display_time_string().into());
+        ui.set_is_running(timer.is_running());
+        ui.set_is_completed(timer.is_completed());
+        ui.set_is_overtime(timer.get_state().is_overtime());
+        ui.set_laps(lap_rows(&timer));
+        update_target_ui(&ui, &timer);
+    }
+    let config = Rc::new(RefCell::new(config));
+    
+    // Set up export menu callback, writing the history to the exports folder
+    {
+        let ui_weak = ui.as_weak();
+        let history_clone = history.clone();
+        ui.on_export_clicked(move |format| {
+            let ui = ui_weak.unwrap();
+            let format = match format {
+                1 => ExportFormat::Json,
+                2 => ExportFormat::Ics,
+                _ => ExportFormat::Csv,
+            };
+            let Some(dir) = data_dir() else {
+                ui.set_export_message("Could not find a folder to export to".into());
+                return;
+            };
+            let file_name = format!("history-{}.{}", Local::now().format("%Y%m%d-%H%M%S"), format.extension());
+            let path = dir.join("exports").join(file_name);
+            let message = match write_export(&history_clone.borrow(), &path) {
+                Ok(()) => format!("Exported to {}", path.display()),
+                Err(error) => format!("Could not export to {}: {}", path.display(), error),
+            };
+            ui.set_export_message(message.into());
+        });
+    }
+    
+    // Set up label callback, applying to the run in progress
+    {
+        let timer_logic_clone = timer_logic.clone();
+        ui.on_label_changed(move |label| {
+            timer_logic_clone.borrow_mut().set_label(Some(label.to_string()));
+        });
+    }
     
     // Set up time input change callback
     {
         let ui_weak = ui.as_weak();
         let timer_logic_clone = timer_logic.clone();
-        ui.on_time_input_changed(move |hours, minutes, seconds| {
+        ui.on_time_input_changed(move |days, hours, minutes, seconds| {
             let ui = ui_weak.unwrap();
             let mut timer = timer_logic_clone.borrow_mut();
             
             // Sanitize inputs - ensure non-negative values
+            let days = if days < 0 { 0 } else { days as u32 };
             let hours = if hours < 0 { 0 } else { hours as u32 };
             let minutes = if minutes < 0 { 0 } else { minutes as u32 };
             let seconds = if seconds < 0 { 0 } else { seconds as u32 };
             
             // Validate and set time
-            match timer.set_time(hours, minutes, seconds) {
+            match timer.set_time_with_days(days, hours, minutes, seconds) {
                 Ok(()) => {
                     let time_str = timer.get_remaining_time_string();
                     ui.set_display_time(time_str.into());
                     ui.set_is_completed(false);
+                    ui.set_laps(lap_rows(&timer));
+                    update_target_ui(&ui, &timer);
+                    show_validation_error(&ui, None);
                 }
                 Err(error) => {
-                    // Handle validation error with graceful recovery
-                    println!("Timer validation error: {}", error);
                     // Keep the previous valid state - don't update display
+                    show_validation_error(&ui, Some(&error));
+                }
+            }
+        });
+    }
+    
+    // Set up quick-entry callback, parsing free-form durations like "1h30m" or
+    // wall-clock targets like "until 14:30"
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        ui.on_quick_entry_accepted(move |text| {
+            let ui = ui_weak.unwrap();
+            let mut timer = timer_logic_clone.borrow_mut();
+            
+            let result = parse_timer_input(&text).and_then(|input| match input {
+                TimerInput::Duration(duration) => timer.set_duration(duration),
+                TimerInput::Until(time) => timer.set_target_time(time),
+            });
+            match result {
+                Ok(()) => {
+                    // Mirror the parsed duration in the separate unit fields
+                    let state = timer.get_state();
+                    ui.set_input_days(state.days as i32);
+                    ui.set_input_hours(state.hours as i32);
+                    ui.set_input_minutes(state.minutes as i32);
+                    ui.set_input_seconds(state.seconds as i32);
+                    ui.set_display_time(timer.get_remaining_time_string().into());
+                    ui.set_is_running(timer.is_running());
+                    ui.set_is_completed(false);
+                    ui.set_laps(lap_rows(&timer));
+                    update_target_ui(&ui, &timer);
+                    show_validation_error(&ui, None);
+                }
+                Err(error) => {
+                    show_validation_error(&ui, Some(&error));
+                    // Point at the quick-entry field rather than the unit fields
+                    ui.set_error_field(6);
                 }
             }
         });
@@ -54,42 +345,70 @@ fn main() -> Result<(), slint::PlatformError> {
     {
         let ui_weak = ui.as_weak();
         let timer_logic_clone = timer_logic.clone();
+        let pomodoro_clone = pomodoro.clone();
+        let intervals_clone = intervals.clone();
+        let config_clone = config.clone();
         ui.on_start_pause_clicked(move || {
             let ui = ui_weak.unwrap();
             let mut timer = timer_logic_clone.borrow_mut();
+            let mut pomodoro = pomodoro_clone.borrow_mut();
+            let in_intervals = intervals_clone.borrow().is_some();
+            let was_running = timer.is_running();
             
-            if timer.is_running() {
+            if was_running {
                 timer.pause_timer();
-                println!("Timer paused");
+            } else if let Some(pomodoro) = pomodoro.as_mut() {
+                // Pomodoro phases are loaded by the controller, never from the inputs
+                if pomodoro.is_awaiting_confirmation() {
+                    pomodoro.confirm(&mut timer);
+                } else {
+                    timer.start_timer();
+                }
+                update_pomodoro_ui(&ui, pomodoro);
+            } else if in_intervals
+                || timer.mode() == TimerMode::Stopwatch
+                || timer.get_state().target.is_some()
+                || timer.is_paused()
+            {
+                // Interval segments, stopwatches and wall-clock targets have no
+                // input to apply, and paused runs resume as-is
+                timer.start_timer();
             } else {
                 // Before starting, ensure timer has the current input values
-                let hours = ui.get_input_hours() as u32;
-                let minutes = ui.get_input_minutes() as u32;
-                let seconds = ui.get_input_seconds() as u32;
-                
-                println!("Starting timer with: {}:{}:{}", hours, minutes, seconds);
+                let days = ui.get_input_days().max(0) as u32;
+                let hours = ui.get_input_hours().max(0) as u32;
+                let minutes = ui.get_input_minutes().max(0) as u32;
+                let seconds = ui.get_input_seconds().max(0) as u32;
                 
                 // Always set the time from current inputs when starting
-                match timer.set_time(hours, minutes, seconds) {
+                match timer.set_time_with_days(days, hours, minutes, seconds) {
                     Ok(()) => {
                         let time_str = timer.get_remaining_time_string();
                         ui.set_display_time(time_str.into());
                         ui.set_is_completed(false);
+                        ui.set_laps(lap_rows(&timer));
+                        update_target_ui(&ui, &timer);
+                        show_validation_error(&ui, None);
                         
                         timer.start_timer();
-                        println!("Timer started, is_running: {}, remaining: {}", 
-                                timer.is_running(), timer.get_state().remaining_seconds);
                     }
                     Err(error) => {
-                        println!("Timer validation error: {}", error);
+                        show_validation_error(&ui, Some(&error));
                         return; // Don't start if invalid time
                     }
                 }
             }
             
+            // Only run a hook when the click actually started or paused the timer
+            if timer.is_running() != was_running {
+                let event = if was_running { HookEvent::Pause } else { HookEvent::Start };
+                run_hook(&config_clone.borrow().hooks, event, timer.label());
+            }
+            
             // Update UI state
             ui.set_is_running(timer.is_running());
             ui.set_is_completed(timer.is_completed());
+            ui.set_is_overtime(timer.get_state().is_overtime());
         });
     }
     
@@ -97,52 +416,949 @@ fn main() -> Result<(), slint::PlatformError> {
     {
         let ui_weak = ui.as_weak();
         let timer_logic_clone = timer_logic.clone();
+        let intervals_clone = intervals.clone();
         ui.on_reset_clicked(move || {
             let ui = ui_weak.unwrap();
             let mut timer = timer_logic_clone.borrow_mut();
             
-            timer.reset_timer();
+            // Resetting an interval program starts it over from the first segment
+            match intervals_clone.borrow_mut().as_mut() {
+                Some(runner) => {
+                    runner.begin(&mut timer);
+                    update_segment_ui(&ui, runner);
+                }
+                None => timer.reset_timer(),
+            }
             
             // Update UI state
-            let time_str = timer.get_remaining_time_string();
+            let time_str = timer.get_display_time_string();
             ui.set_display_time(time_str.into());
             ui.set_is_running(timer.is_running());
             ui.set_is_completed(timer.is_completed());
+            ui.set_is_overtime(timer.get_state().is_overtime());
+            ui.set_laps(lap_rows(&timer));
+        });
+    }
+    
+    // Set up lap button callback
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        ui.on_lap_clicked(move || {
+            let ui = ui_weak.unwrap();
+            let mut timer = timer_logic_clone.borrow_mut();
+            
+            if timer.lap().is_some() {
+                ui.set_laps(lap_rows(&timer));
+            }
         });
     }
     
-    // Set up real-time timer updates with direct UI updates
+    // Set up real-time timer updates with direct UI updates. The countdown is
+    // computed from elapsed time, so polling faster than the display precision
+    // only keeps the display from lagging behind the real remaining time.
     let ui_weak = ui.as_weak();
     let timer_logic_clone = timer_logic.clone();
-    let timer = slint::Timer::default();
-    timer.start(slint::TimerMode::Repeated, std::time::Duration::from_secs(1), move || {
+    let pomodoro_clone = pomodoro.clone();
+    let intervals_clone = intervals.clone();
+    let timer_manager_clone = timer_manager.clone();
+    let named_timer_rows_clone = named_timer_rows.clone();
+    let history_clone = history.clone();
+    let history_rows_clone = history_rows.clone();
+    let config_clone = config.clone();
+    let timer = Rc::new(slint::Timer::default());
+    let refresh_interval = tick_interval(&config.borrow(), timer_logic.borrow().display_precision());
+    timer.start(slint::TimerMode::Repeated, refresh_interval, move || {
         if let Some(ui) = ui_weak.upgrade() {
             let mut timer_logic = timer_logic_clone.borrow_mut();
-            let was_running = timer_logic.is_running();
-            let old_remaining = timer_logic.get_state().remaining_seconds;
+            let config = config_clone.borrow();
             
-            timer_logic.tick();
+            if let Some(pomodoro) = pomodoro_clone.borrow_mut().as_mut() {
+                if pomodoro.tick(&mut timer_logic).is_some() {
+                    update_pomodoro_ui(&ui, pomodoro);
+                    play_sound(&config.sounds, config.sounds.phase.as_deref());
+                }
+            } else if let Some(runner) = intervals_clone.borrow_mut().as_mut() {
+                match runner.tick(&mut timer_logic) {
+                    Some(SequenceEvent::SegmentStarted(_)) => {
+                        update_segment_ui(&ui, runner);
+                        play_sound(&config.sounds, config.sounds.phase.as_deref());
+                    }
+                    Some(SequenceEvent::Finished) => {
+                        ui.set_segment_label("Done".into());
+                    }
+                    None => {}
+                }
+            } else {
+                timer_logic.tick();
+            }
             
             // Update UI directly after tick
             let state = timer_logic.get_state();
-            let time_str = format!("{:02}:{:02}:{:02}", 
-                state.remaining_seconds / 3600,
-                (state.remaining_seconds % 3600) / 60,
-                state.remaining_seconds % 60
-            );
             
-            if was_running {
-                println!("Tick: {} -> {}, running: {}", old_remaining, state.remaining_seconds, state.is_running);
-            }
-            
-            ui.set_display_time(time_str.into());
+            ui.set_display_time(timer_logic.get_display_time_string().into());
             ui.set_is_running(state.is_running);
             ui.set_is_completed(state.is_completed);
+            ui.set_is_overtime(state.is_overtime());
+            
+            // Save and show runs that ended since the last tick
+            for record in session_receiver.try_iter() {
+                match record.outcome {
+                    SessionOutcome::Completed => {
+                        play_sound(&config.sounds, config.sounds.completed.as_deref());
+                        run_hook(&config.hooks, HookEvent::Complete, record.label.as_deref());
+                    }
+                    SessionOutcome::Reset => run_hook(&config.hooks, HookEvent::Reset, record.label.as_deref()),
+                    SessionOutcome::Abandoned => {}
+                }
+                if let Some(history_store) = &history_store
+                    && let Err(error) = history_store.add_session(&record)
+                {
+                    eprintln!("Could not save history to {}: {}", history_store.history_path().display(), error);
+                }
+                history_rows_clone.insert(0, history_row(&record));
+                let mut history = history_clone.borrow_mut();
+                history.push(record);
+                update_stats_ui(&ui, &history);
+            }
+            
+            // Tick the named timers on the same schedule
+            let mut timer_manager = timer_manager_clone.borrow_mut();
+            timer_manager.tick();
+            sync_named_timer_rows(&named_timer_rows_clone, &timer_manager);
         }
     });
     
+    // Set up display precision callback, refreshing faster for finer precisions
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        let timer = timer.clone();
+        let config_clone = config.clone();
+        let store_clone = store.clone();
+        ui.on_precision_changed(move |index| {
+            let ui = ui_weak.unwrap();
+            let mut timer_logic = timer_logic_clone.borrow_mut();
+            
+            let precision = display_precision(index);
+            timer_logic.set_display_precision(precision);
+            save_setting(store_clone.as_deref(), "display_precision", &index.to_string());
+            timer.set_interval(tick_interval(&config_clone.borrow(), precision));
+            
+            ui.set_display_time(timer_logic.get_display_time_string().into());
+            ui.set_laps(lap_rows(&timer_logic));
+        });
+    }
+    
+    // Set up overtime toggle callback
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        ui.on_overtime_toggled(move |enabled| {
+            let ui = ui_weak.unwrap();
+            let mut timer = timer_logic_clone.borrow_mut();
+            
+            timer.set_overtime(enabled);
+            
+            ui.set_is_running(timer.is_running());
+            ui.set_is_completed(timer.is_completed());
+        });
+    }
+    
+    // Set up countdown/stopwatch mode callback
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        let pomodoro_clone = pomodoro.clone();
+        let intervals_clone = intervals.clone();
+        ui.on_mode_changed(move |mode| {
+            let ui = ui_weak.unwrap();
+            let mut timer = timer_logic_clone.borrow_mut();
+            let mut pomodoro = pomodoro_clone.borrow_mut();
+            let mut intervals = intervals_clone.borrow_mut();
+            *pomodoro = None;
+            *intervals = None;
+            show_validation_error(&ui, None);
+            
+            match mode {
+                1 => timer.set_mode(TimerMode::Stopwatch),
+                2 => match Pomodoro::new(pomodoro_config(&ui)) {
+                    Ok(mut session) => {
+                        session.begin(&mut timer);
+                        update_pomodoro_ui(&ui, &session);
+                        *pomodoro = Some(session);
+                    }
+                    Err(error) => show_validation_error(&ui, Some(&error)),
+                },
+                3 => match SequenceRunner::new(interval_sequence(&ui)) {
+                    Ok(mut runner) => {
+                        runner.begin(&mut timer);
+                        update_segment_ui(&ui, &runner);
+                        *intervals = Some(runner);
+                    }
+                    Err(error) => show_validation_error(&ui, Some(&error)),
+                },
+                _ => {
+                    timer.set_mode(TimerMode::Countdown);
+                    // Restore the countdown from the current inputs
+                    let days = ui.get_input_days().max(0) as u32;
+                    let hours = ui.get_input_hours().max(0) as u32;
+                    let minutes = ui.get_input_minutes().max(0) as u32;
+                    let seconds = ui.get_input_seconds().max(0) as u32;
+                    if let Err(error) = timer.set_time_with_days(days, hours, minutes, seconds) {
+                        show_validation_error(&ui, Some(&error));
+                    }
+                }
+            }
+            
+            ui.set_display_time(timer.get_display_time_string().into());
+            ui.set_is_running(timer.is_running());
+            ui.set_is_completed(timer.is_completed());
+            ui.set_is_overtime(timer.get_state().is_overtime());
+            ui.set_laps(lap_rows(&timer));
+            update_target_ui(&ui, &timer);
+        });
+    }
+    
+    // Set up Pomodoro settings callback, restarting the session with the new lengths
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        let pomodoro_clone = pomodoro.clone();
+        let store_clone = store.clone();
+        ui.on_pomodoro_config_changed(move || {
+            let ui = ui_weak.unwrap();
+            let mut timer = timer_logic_clone.borrow_mut();
+            let mut pomodoro = pomodoro_clone.borrow_mut();
+            if pomodoro.is_none() {
+                return;
+            }
+            
+            match Pomodoro::new(pomodoro_config(&ui)) {
+                Ok(mut session) => {
+                    if let Ok(json) = to_json(session.config()) {
+                        save_setting(store_clone.as_deref(), "pomodoro", &json);
+                    }
+                    session.begin(&mut timer);
+                    update_pomodoro_ui(&ui, &session);
+                    *pomodoro = Some(session);
+                    
+                    ui.set_display_time(timer.get_display_time_string().into());
+                    ui.set_is_running(timer.is_running());
+                    ui.set_is_completed(timer.is_completed());
+                    show_validation_error(&ui, None);
+                }
+                Err(error) => {
+                    // Keep the previous valid session
+                    show_validation_error(&ui, Some(&error));
+                }
+            }
+        });
+    }
+    
+    // Set up Pomodoro skip callback
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        let pomodoro_clone = pomodoro.clone();
+        ui.on_pomodoro_skip_clicked(move || {
+            let ui = ui_weak.unwrap();
+            let mut timer = timer_logic_clone.borrow_mut();
+            
+            if let Some(pomodoro) = pomodoro_clone.borrow_mut().as_mut() {
+                pomodoro.skip(&mut timer);
+                update_pomodoro_ui(&ui, pomodoro);
+                
+                ui.set_display_time(timer.get_display_time_string().into());
+                ui.set_is_running(timer.is_running());
+                ui.set_is_completed(timer.is_completed());
+            }
+        });
+    }
+    
+    // Set up interval program settings callback, restarting the program with the new settings
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        let intervals_clone = intervals.clone();
+        ui.on_interval_config_changed(move || {
+            let ui = ui_weak.unwrap();
+            let mut timer = timer_logic_clone.borrow_mut();
+            let mut intervals = intervals_clone.borrow_mut();
+            if intervals.is_none() {
+                return;
+            }
+            
+            match SequenceRunner::new(interval_sequence(&ui)) {
+                Ok(mut runner) => {
+                    runner.begin(&mut timer);
+                    update_segment_ui(&ui, &runner);
+                    *intervals = Some(runner);
+                    
+                    ui.set_display_time(timer.get_display_time_string().into());
+                    ui.set_is_running(timer.is_running());
+                    ui.set_is_completed(timer.is_completed());
+                    show_validation_error(&ui, None);
+                }
+                Err(error) => {
+                    // Keep the previous valid program
+                    show_validation_error(&ui, Some(&error));
+                }
+            }
+        });
+    }
+    
+    // Set up named timer callbacks
+    {
+        let ui_weak = ui.as_weak();
+        let timer_manager_clone = timer_manager.clone();
+        let named_timer_rows_clone = named_timer_rows.clone();
+        ui.on_named_timer_add_clicked(move |name| {
+            let ui = ui_weak.unwrap();
+            let mut timer_manager = timer_manager_clone.borrow_mut();
+            
+            let name = match name.trim() {
+                "" => format!("Timer {}", timer_manager.len() + 1),
+                name => name.to_string(),
+            };
+            let days = ui.get_input_days().max(0) as u32;
+            let hours = ui.get_input_hours().max(0) as u32;
+            let minutes = ui.get_input_minutes().max(0) as u32;
+            let seconds = ui.get_input_seconds().max(0) as u32;
+            
+            match timer_manager.add_with_days(name, days, hours, minutes, seconds) {
+                Ok(_) => {
+                    ui.set_new_timer_name("".into());
+                    show_validation_error(&ui, None);
+                    sync_named_timer_rows(&named_timer_rows_clone, &timer_manager);
+                }
+                Err(error) => show_validation_error(&ui, Some(&error)),
+            }
+        });
+    }
+    {
+        let timer_manager_clone = timer_manager.clone();
+        let named_timer_rows_clone = named_timer_rows.clone();
+        ui.on_named_timer_start_pause_clicked(move |id| {
+            let mut timer_manager = timer_manager_clone.borrow_mut();
+            if let Some(timer) = timer_manager.get_mut(TimerId(id as u64)) {
+                if timer.is_running() {
+                    timer.pause_timer();
+                } else {
+                    timer.start_timer();
+                }
+            }
+            sync_named_timer_rows(&named_timer_rows_clone, &timer_manager);
+        });
+    }
+    {
+        let timer_manager_clone = timer_manager.clone();
+        let named_timer_rows_clone = named_timer_rows.clone();
+        ui.on_named_timer_reset_clicked(move |id| {
+            let mut timer_manager = timer_manager_clone.borrow_mut();
+            if let Some(timer) = timer_manager.get_mut(TimerId(id as u64)) {
+                timer.reset_timer();
+            }
+            sync_named_timer_rows(&named_timer_rows_clone, &timer_manager);
+        });
+    }
+    {
+        let timer_manager_clone = timer_manager.clone();
+        let named_timer_rows_clone = named_timer_rows.clone();
+        ui.on_named_timer_delete_clicked(move |id| {
+            let mut timer_manager = timer_manager_clone.borrow_mut();
+            timer_manager.remove(TimerId(id as u64));
+            sync_named_timer_rows(&named_timer_rows_clone, &timer_manager);
+        });
+    }
+    
+    // Set up preset callbacks, saving the list after every change
+    {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        let pomodoro_clone = pomodoro.clone();
+        let intervals_clone = intervals.clone();
+        let presets_clone = presets.clone();
+        let config_clone = config.clone();
+        ui.on_preset_clicked(move |index| {
+            let ui = ui_weak.unwrap();
+            let presets = presets_clone.borrow();
+            let Some(preset) = presets.presets().get(index as usize) else {
+                return;
+            };
+            let mut timer = timer_logic_clone.borrow_mut();
+            
+            // Presets are countdowns, so leave the other modes first
+            *pomodoro_clone.borrow_mut() = None;
+            *intervals_clone.borrow_mut() = None;
+            if timer.mode() != TimerMode::Countdown {
+                timer.set_mode(TimerMode::Countdown);
+            }
+            ui.set_timer_mode(0);
+            
+            match preset.apply(&mut timer, ui.get_preset_auto_start()) {
+                Ok(()) => {
+                    if timer.is_running() {
+                        run_hook(&config_clone.borrow().hooks, HookEvent::Start, timer.label());
+                    }
+                    // Mirror the preset's duration in the unit fields
+                    let state = timer.get_state();
+                    ui.set_input_days(state.days as i32);
+                    ui.set_input_hours(state.hours as i32);
+                    ui.set_input_minutes(state.minutes as i32);
+                    ui.set_input_seconds(state.seconds as i32);
+                    ui.set_display_time(timer.get_display_time_string().into());
+                    ui.set_is_running(timer.is_running());
+                    ui.set_is_completed(false);
+                    ui.set_is_overtime(false);
+                    ui.set_laps(lap_rows(&timer));
+                    update_target_ui(&ui, &timer);
+                    show_validation_error(&ui, None);
+                }
+                Err(error) => show_validation_error(&ui, Some(&error)),
+            }
+        });
+    }
+    {
+        let ui_weak = ui.as_weak();
+        let presets_clone = presets.clone();
+        let preset_store_clone = preset_store.clone();
+        let preset_rows_clone = preset_rows.clone();
+        ui.on_preset_add_clicked(move |name| {
+            let ui = ui_weak.unwrap();
+            let mut presets = presets_clone.borrow_mut();
+            
+            let days = ui.get_input_days().max(0) as u32;
+            let hours = ui.get_input_hours().max(0) as u32;
+            let minutes = ui.get_input_minutes().max(0) as u32;
+            let seconds = ui.get_input_seconds().max(0) as u32;
+            let result = validate_time_with_days(days, hours, minutes, seconds).and_then(|()| {
+                let total = u64::from(days) * 86_400 + u64::from(hours) * 3600 + u64::from(minutes) * 60 + u64::from(seconds);
+                presets.add(Preset::new(name.as_str(), Duration::from_secs(total))?)
+            });
+            match result {
+                Ok(()) => {
+                    ui.set_new_preset_name("".into());
+                    show_validation_error(&ui, None);
+                    save_presets(preset_store_clone.as_ref().as_deref(), &presets, &preset_rows_clone);
+                }
+                Err(error) => show_validation_error(&ui, Some(&error)),
+            }
+        });
+    }
+    {
+        let ui_weak = ui.as_weak();
+        let presets_clone = presets.clone();
+        let preset_store_clone = preset_store.clone();
+        let preset_rows_clone = preset_rows.clone();
+        ui.on_preset_renamed(move |index, name| {
+            let ui = ui_weak.unwrap();
+            let mut presets = presets_clone.borrow_mut();
+            match presets.rename(index as usize, name.as_str()) {
+                Ok(true) => {
+                    ui.set_new_preset_name("".into());
+                    show_validation_error(&ui, None);
+                    save_presets(preset_store_clone.as_ref().as_deref(), &presets, &preset_rows_clone);
+                }
+                Ok(false) => {}
+                Err(error) => show_validation_error(&ui, Some(&error)),
+            }
+        });
+    }
+    {
+        let presets_clone = presets.clone();
+        let preset_store_clone = preset_store.clone();
+        let preset_rows_clone = preset_rows.clone();
+        ui.on_preset_moved(move |from, to| {
+            let mut presets = presets_clone.borrow_mut();
+            if presets.move_preset(from as usize, to as usize) {
+                save_presets(preset_store_clone.as_ref().as_deref(), &presets, &preset_rows_clone);
+            }
+        });
+    }
+    {
+        let presets_clone = presets.clone();
+        let preset_store_clone = preset_store.clone();
+        let preset_rows_clone = preset_rows.clone();
+        ui.on_preset_delete_clicked(move |index| {
+            let mut presets = presets_clone.borrow_mut();
+            if presets.remove(index as usize).is_some() {
+                save_presets(preset_store_clone.as_ref().as_deref(), &presets, &preset_rows_clone);
+            }
+        });
+    }
+    
+    // Reload the configuration when the file changes, keeping the previous
+    // settings while the new file is invalid. The [timer] defaults only apply
+    // at start-up, apart from the tick interval
+    let config_timer = slint::Timer::default();
+    if let Some(mut config_file) = config_file {
+        let ui_weak = ui.as_weak();
+        let timer_logic_clone = timer_logic.clone();
+        let config_clone = config.clone();
+        let timer = timer.clone();
+        let mut showing_error = !ui.get_error_message().is_empty();
+        config_timer.start(slint::TimerMode::Repeated, Duration::from_secs(1), move || {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+            match config_file.reload_if_changed() {
+                Some(Ok(config)) => {
+                    eprintln!("Reloaded configuration from {}", config_file.path().display());
+                    apply_appearance(&ui, &config);
+                    timer.set_interval(tick_interval(&config, timer_logic_clone.borrow().display_precision()));
+                    *config_clone.borrow_mut() = config;
+                    if showing_error {
+                        show_validation_error(&ui, None);
+                        showing_error = false;
+                    }
+                }
+                Some(Err(error)) => {
+                    show_config_error(&ui, config_file.path(), &error);
+                    showing_error = true;
+                }
+                None => {}
+            }
+        });
+    }
+    
     // Keep the timer alive by storing it
     std::mem::forget(timer);
     
     ui.run()
 }
+
+/// Shows a timer restored from the previous session, filling the inputs from
+/// its configured duration
+fn show_restored_timer(ui: &TimerWindow, timer: &TimerLogic) {
+    let state = timer.get_state();
+    if state.mode == TimerMode::Stopwatch {
+        ui.set_timer_mode(1);
+    } else {
+        ui.set_input_days(state.days as i32);
+        ui.set_input_hours(state.hours as i32);
+        ui.set_input_minutes(state.minutes as i32);
+        ui.set_input_seconds(state.seconds as i32);
+    }
+    ui.set_overtime_enabled(timer.overtime_enabled());
+    ui.set_session_label(timer.label().unwrap_or_default().into());
+    ui.set_display_time(timer.get_display_time_string().into());
+    ui.set_is_running(timer.is_running());
+    ui.set_is_completed(timer.is_completed());
+    ui.set_is_overtime(state.is_overtime());
+    ui.set_laps(lap_rows(timer));
+    update_target_ui(ui, timer);
+}
+
+/// Shows the wall-clock time a countdown to an absolute time ends at, or
+/// hides it for other timers
+fn update_target_ui(ui: &TimerWindow, timer: &TimerLogic) {
+    let label = match timer.get_state().target {
+        Some(target) => target.format("%a %H:%M").to_string(),
+        None => String::new(),
+    };
+    ui.set_target_label(label.into());
+}
+
+/// Shows a validation error under the input panel and highlights the offending
+/// time input, or clears both once the settings are valid
+fn show_validation_error(ui: &TimerWindow, error: Option<&TimerError>) {
+    let field = match error {
+        Some(TimerError::HoursOutOfRange(_)) => 1,
+        Some(TimerError::MinutesOutOfRange(_)) => 2,
+        Some(TimerError::SecondsOutOfRange(_)) => 3,
+        Some(TimerError::ZeroDuration) => 4,
+        Some(TimerError::DaysOutOfRange(_)) => 5,
+        _ => 0,
+    };
+    ui.set_error_field(field);
+    ui.set_error_message(error.map(ToString::to_string).unwrap_or_default().into());
+}
+
+/// Shows why the configuration file could not be read
+fn show_config_error(ui: &TimerWindow, path: &Path, error: &dyn Error) {
+    eprintln!("Could not load configuration from {}: {}", path.display(), error);
+    ui.set_error_message(format!("{}: {}", path.display(), error).into());
+    ui.set_error_field(0);
+}
+
+/// Applies the configured theme colors and window size
+fn apply_appearance(ui: &TimerWindow, config: &Config) {
+    let colors = config.theme.colors();
+    let theme = ui.global::<Theme>();
+    theme.set_dark(config.theme.name == ThemeName::Dark);
+    theme.set_background(rgb_color(colors.background));
+    theme.set_panel(rgb_color(colors.panel));
+    theme.set_border(rgb_color(colors.border));
+    theme.set_text(rgb_color(colors.text));
+    theme.set_muted(rgb_color(colors.muted));
+    theme.set_accent(rgb_color(colors.accent));
+    theme.set_running(rgb_color(colors.running));
+    theme.set_completed(rgb_color(colors.completed));
+    theme.set_alert(rgb_color(colors.alert));
+    
+    let size = slint::LogicalSize::new(config.window.width as f32, config.window.height as f32);
+    ui.window().set_size(size);
+}
+
+/// Converts a `0xRRGGBB` color
+fn rgb_color(rgb: u32) -> slint::Color {
+    let [_, red, green, blue] = rgb.to_be_bytes();
+    slint::Color::from_rgb_u8(red, green, blue)
+}
+
+/// Gets how often the display is refreshed: the configured interval, or
+/// often enough to show every unit of `precision`
+fn tick_interval(config: &Config, precision: DisplayPrecision) -> Duration {
+    config.timer.tick_interval.unwrap_or_else(|| precision.refresh_interval())
+}
+
+/// Plays `sound` with the configured player in the background, if sounds are enabled
+fn play_sound(sounds: &SoundSettings, sound: Option<&Path>) {
+    let Some(sound) = sound.filter(|_| sounds.enabled) else {
+        return;
+    };
+    let mut player = Command::new(&sounds.player);
+    player.arg(sound);
+    if let Err(error) = spawn_detached(&mut player) {
+        eprintln!("Could not play {} with {}: {}", sound.display(), sounds.player, error);
+    }
+}
+
+/// Runs the configured hook command for `event` in the background, telling
+/// it the event and the session label in `TIMER_EVENT` and `TIMER_LABEL`
+fn run_hook(hooks: &HookSettings, event: HookEvent, label: Option<&str>) {
+    let Some(command) = hooks.command(event) else {
+        return;
+    };
+    let mut shell = if cfg!(windows) { Command::new("cmd") } else { Command::new("sh") };
+    shell
+        .arg(if cfg!(windows) { "/C" } else { "-c" })
+        .arg(command)
+        .env("TIMER_EVENT", event.key())
+        .env("TIMER_LABEL", label.unwrap_or(""));
+    if let Err(error) = spawn_detached(&mut shell) {
+        eprintln!("Could not run the {} hook: {}", event.key(), error);
+    }
+}
+
+/// Starts `command` without waiting for it, reaping it on a separate thread
+/// once it exits
+fn spawn_detached(command: &mut Command) -> std::io::Result<()> {
+    let mut child = command.spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Builds a history list row for a finished run
+fn history_row(record: &SessionRecord) -> HistoryRow {
+    HistoryRow {
+        started: record.started_at.format("%a %d %b %H:%M").to_string().into(),
+        label: record.label.clone().unwrap_or_default().into(),
+        elapsed: DisplayPrecision::Seconds.format_elapsed(record.elapsed).into(),
+        outcome: record.outcome.label().into(),
+        is_completed: record.outcome == SessionOutcome::Completed,
+    }
+}
+
+/// Builds a preset list row
+fn preset_row(preset: &Preset) -> PresetRow {
+    PresetRow {
+        name: preset.name.clone().into(),
+        duration: DisplayPrecision::Seconds.format(preset.duration).into(),
+    }
+}
+
+/// Saves the presets, if there is somewhere to save them, and shows them in the preset list
+fn save_presets(store: Option<&Store>, presets: &PresetList, rows: &VecModel<PresetRow>) {
+    if let Some(store) = store
+        && let Err(error) = store.save_presets(presets)
+    {
+        eprintln!("Could not save presets to {}: {}", store.presets_path().display(), error);
+    }
+    rows.set_vec(presets.presets().iter().map(preset_row).collect::<Vec<_>>());
+}
+
+/// Saves a setting, if there is somewhere to save it
+fn save_setting(store: Option<&Store>, key: &str, value: &str) {
+    if let Some(store) = store
+        && let Err(error) = store.set_setting(key, value)
+    {
+        eprintln!("Could not save the {} setting: {}", key, error);
+    }
+}
+
+/// Applies the display precision and Pomodoro lengths saved last time;
+/// settings that are missing or invalid keep their defaults
+fn load_settings(ui: &TimerWindow, timer: &mut TimerLogic, store: &Store) {
+    let setting = |key: &str| match store.setting(key) {
+        Ok(value) => value,
+        Err(error) => {
+            eprintln!("Could not load the {} setting: {}", key, error);
+            None
+        }
+    };
+    if let Some(index) = setting("display_precision").and_then(|value| value.parse::<i32>().ok()) {
+        ui.set_display_precision(index);
+        timer.set_display_precision(display_precision(index));
+    }
+    if let Some(config) = setting("pomodoro").and_then(|json| from_json::<PomodoroConfig>(&json).ok()) {
+        ui.set_pomodoro_work_minutes((config.work.as_secs() / 60) as i32);
+        ui.set_pomodoro_short_break_minutes((config.short_break.as_secs() / 60) as i32);
+        ui.set_pomodoro_long_break_minutes((config.long_break.as_secs() / 60) as i32);
+        ui.set_pomodoro_long_break_interval(config.long_break_interval as i32);
+        ui.set_pomodoro_auto_advance(config.auto_advance);
+    }
+}
+
+/// Gets the display precision at `index` in the precision menu
+fn display_precision(index: i32) -> DisplayPrecision {
+    match index {
+        1 => DisplayPrecision::Tenths,
+        2 => DisplayPrecision::Hundredths,
+        _ => DisplayPrecision::Seconds,
+    }
+}
+
+/// Finds the saved preset called `name`, ignoring case
+fn load_preset(name: &str) -> Result<Preset, Box<dyn Error>> {
+    let presets = Store::open()?.presets()?;
+    let preset = presets.find(name).ok_or_else(|| TimerError::UnknownPreset(name.to_string()))?;
+    Ok(preset.clone())
+}
+
+/// Exports the saved session history to `path`, returning the process exit code
+fn export_command(path: &Path) -> i32 {
+    let records = match Store::open().and_then(|store| store.history()) {
+        Ok(records) => records,
+        Err(error) => {
+            eprintln!("Could not load history: {}", error);
+            return 1;
+        }
+    };
+    if let Err(error) = write_export(&records, path) {
+        eprintln!("Could not export history to {}: {}", path.display(), error);
+        return 1;
+    }
+    println!("Exported {} sessions to {}", records.len(), path.display());
+    0
+}
+
+/// Adds the sessions in `path` to the saved session history, skipping ones
+/// already there, and reports every invalid entry; returns the process exit
+/// code, which is non-zero if any entry was invalid
+fn import_history_command(path: &Path) -> i32 {
+    let read = || -> Result<_, Box<dyn Error>> {
+        let format = ImportFormat::from_path(path)?;
+        let store = Store::open()?;
+        let existing = store.history()?;
+        Ok((format, fs::read_to_string(path)?, store, existing))
+    };
+    let (format, text, store, existing) = match read() {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            eprintln!("Could not import history from {}: {}", path.display(), error);
+            return 1;
+        }
+    };
+    
+    let report = import_sessions(&text, format, &existing);
+    for error in &report.errors {
+        eprintln!("{}: {}", path.display(), error);
+    }
+    for record in &report.imported {
+        if let Err(error) = store.add_session(record) {
+            eprintln!("Could not save history to {}: {}", store.history_path().display(), error);
+            return 1;
+        }
+    }
+    println!(
+        "Imported {} sessions, skipped {} already in the history and {} invalid",
+        report.imported.len(),
+        report.duplicates,
+        report.errors.len()
+    );
+    i32::from(!report.errors.is_empty())
+}
+
+/// Adds the presets in `path` to the saved presets, skipping names already
+/// taken, and reports every invalid entry; returns the process exit code,
+/// which is non-zero if any entry was invalid
+fn import_presets_command(path: &Path) -> i32 {
+    let read = || -> Result<_, Box<dyn Error>> {
+        let format = ImportFormat::from_path(path)?;
+        let store = Store::open()?;
+        let presets = store.presets()?;
+        Ok((format, fs::read_to_string(path)?, store, presets))
+    };
+    let (format, text, store, mut presets) = match read() {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            eprintln!("Could not import presets from {}: {}", path.display(), error);
+            return 1;
+        }
+    };
+    
+    let report = import_presets(&text, format, presets.presets());
+    for error in &report.errors {
+        eprintln!("{}: {}", path.display(), error);
+    }
+    let imported = report.imported.len();
+    for preset in report.imported {
+        // Names were checked against the saved presets and each other
+        let _ = presets.add(preset);
+    }
+    if let Err(error) = store.save_presets(&presets) {
+        eprintln!("Could not save presets to {}: {}", store.presets_path().display(), error);
+        return 1;
+    }
+    println!(
+        "Imported {} presets, skipped {} already saved and {} invalid",
+        imported,
+        report.duplicates,
+        report.errors.len()
+    );
+    i32::from(!report.errors.is_empty())
+}
+
+/// Writes `records` to `path` in the format its extension names
+fn write_export(records: &[SessionRecord], path: &Path) -> Result<(), Box<dyn Error>> {
+    let format = ExportFormat::from_path(path)?;
+    let contents = export_history(records, format, Local::now().fixed_offset())?;
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Shows totals for the last 7 days and 8 weeks, the completion rate, the
+/// average session length and the streaks in the statistics view
+fn update_stats_ui(ui: &TimerWindow, records: &[SessionRecord]) {
+    let today = Local::now().date_naive();
+    let days = daily_totals(records, today - TimeDelta::days(6), today);
+    ui.set_daily_bars(chart_bars(&days, |day| day.format("%a").to_string()));
+    
+    let this_monday = today - TimeDelta::days(i64::from(today.weekday().num_days_from_monday()));
+    let weeks = weekly_totals(records, this_monday - TimeDelta::weeks(7), today);
+    ui.set_weekly_bars(chart_bars(&weeks, |monday| monday.format("%d/%m").to_string()));
+    
+    let rate = completion_rate(records).map(|rate| format!("{:.0}%", rate * 100.0));
+    ui.set_completion_rate(rate.unwrap_or_else(|| "—".to_string()).into());
+    let average = average_length(records).map(short_duration);
+    ui.set_average_length(average.unwrap_or_else(|| "—".to_string()).into());
+    let streaks = streaks(records, today);
+    ui.set_streak(format!("{}d (best {}d)", streaks.current, streaks.longest).into());
+}
+
+/// Builds chart bars scaled to the largest total
+fn chart_bars(totals: &[PeriodTotal], label: impl Fn(NaiveDate) -> String) -> ModelRc<ChartBar> {
+    let largest = totals.iter().map(|period| period.total).max().unwrap_or_default();
+    let bars: Vec<ChartBar> = totals
+        .iter()
+        .map(|period| ChartBar {
+            label: label(period.start).into(),
+            value: short_duration(period.total).into(),
+            fraction: if largest.is_zero() {
+                0.0
+            } else {
+                (period.total.as_secs_f64() / largest.as_secs_f64()) as f32
+            },
+        })
+        .collect();
+    ModelRc::new(VecModel::from(bars))
+}
+
+/// Formats a duration compactly for charts, e.g. "25m" or "2h05m"
+fn short_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    match minutes / 60 {
+        0 => format!("{}m", minutes),
+        hours => format!("{}h{:02}m", hours, minutes % 60),
+    }
+}
+
+/// Builds the lap table model, newest lap first, marking the fastest and slowest splits
+fn lap_rows(timer: &TimerLogic) -> ModelRc<LapRow> {
+    let state = timer.get_state();
+    let precision = timer.display_precision();
+    let fastest = state.fastest_lap().map(|lap| lap.number);
+    let slowest = state.slowest_lap().map(|lap| lap.number);
+    
+    let rows: Vec<LapRow> = state
+        .laps
+        .iter()
+        .rev()
+        .map(|lap| LapRow {
+            number: lap.number as i32,
+            split: precision.format_elapsed(lap.split).into(),
+            total: precision.format_elapsed(lap.elapsed).into(),
+            is_fastest: fastest == Some(lap.number),
+            is_slowest: slowest == Some(lap.number),
+        })
+        .collect();
+    ModelRc::new(VecModel::from(rows))
+}
+
+/// Reads the Pomodoro lengths and behaviour from the settings panel
+fn pomodoro_config(ui: &TimerWindow) -> PomodoroConfig {
+    let minutes = |value: i32| Duration::from_secs(value.max(0) as u64 * 60);
+    PomodoroConfig {
+        work: minutes(ui.get_pomodoro_work_minutes()),
+        short_break: minutes(ui.get_pomodoro_short_break_minutes()),
+        long_break: minutes(ui.get_pomodoro_long_break_minutes()),
+        long_break_interval: ui.get_pomodoro_long_break_interval().max(0) as u32,
+        auto_advance: ui.get_pomodoro_auto_advance(),
+    }
+}
+
+/// Shows the current Pomodoro phase and progress in the window
+fn update_pomodoro_ui(ui: &TimerWindow, pomodoro: &Pomodoro) {
+    ui.set_pomodoro_phase(pomodoro.phase().label().into());
+    ui.set_pomodoro_cycles(pomodoro.completed_cycles() as i32);
+    ui.set_pomodoro_awaiting(pomodoro.is_awaiting_confirmation());
+}
+
+/// Builds the interval program from the settings panel
+fn interval_sequence(ui: &TimerWindow) -> Sequence {
+    let seconds = |value: i32| Duration::from_secs(value.max(0) as u64);
+    Sequence::interval_training(
+        seconds(ui.get_interval_warmup_seconds()),
+        seconds(ui.get_interval_work_seconds()),
+        seconds(ui.get_interval_rest_seconds()),
+        ui.get_interval_rounds().max(0) as u32,
+        seconds(ui.get_interval_cooldown_seconds()),
+    )
+}
+
+/// Shows the current interval segment's label and color in the window
+fn update_segment_ui(ui: &TimerWindow, runner: &SequenceRunner) {
+    let current = runner.current();
+    ui.set_segment_label(current.announcement().into());
+    ui.set_segment_color(rgb_color(current.segment.color));
+}
+
+/// Updates the named timer list to match the manager, touching only rows that changed
+fn sync_named_timer_rows(model: &VecModel<TimerRow>, timer_manager: &TimerManager) {
+    let rows: Vec<TimerRow> = timer_manager
+        .timers()
+        .iter()
+        .map(|named| TimerRow {
+            id: named.id.0 as i32,
+            name: named.name.as_str().into(),
+            display: named.timer.get_display_time_string().into(),
+            is_running: named.timer.is_running(),
+            is_completed: named.timer.is_completed(),
+        })
+        .collect();
+    
+    if model.row_count() != rows.len() {
+        model.set_vec(rows);
+        return;
+    }
+    for (index, row) in rows.into_iter().enumerate() {
+        if model.row_data(index).as_ref() != Some(&row) {
+            model.set_row_data(index, row);
+        }
+    }
+}
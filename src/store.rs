@@ -0,0 +1,161 @@
+#[cfg(feature = "sqlite")]
+use std::cell::RefCell;
+#[cfg(feature = "sqlite")]
+use std::collections::HashSet;
+use std::error::Error;
+use std::path::Path;
+#[cfg(feature = "sqlite")]
+use std::path::PathBuf;
+
+use rust_slint_timer::timer::{HistoryStore, PresetList, PresetStore, SessionRecord};
+#[cfg(feature = "sqlite")]
+use rust_slint_timer::timer::{Database, SessionQuery};
+
+/// Where the session history, presets and settings are kept
+///
+/// With the `sqlite` feature everything lives in the database at
+/// [`Database::default_path`]. Without it, history and presets are flat
+/// files in the data folder and settings are not kept between runs.
+pub struct Store {
+    #[cfg(feature = "sqlite")]
+    database: RefCell<Database>,
+    #[cfg(feature = "sqlite")]
+    path: PathBuf,
+    #[cfg(not(feature = "sqlite"))]
+    history: HistoryStore,
+    #[cfg(not(feature = "sqlite"))]
+    presets: PresetStore,
+}
+
+#[cfg(feature = "sqlite")]
+impl Store {
+    /// Opens the database, creating it and copying in the history and
+    /// presets from the flat files on first use
+    pub fn open() -> Result<Self, Box<dyn Error>> {
+        let path = Database::default_path().ok_or("Could not find the data folder")?;
+        let store = Self { database: RefCell::new(Database::open(&path)?), path };
+        store.import_flat_files()?;
+        Ok(store)
+    }
+
+    /// Gets the path the session history is saved to
+    pub fn history_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the path the presets are saved to
+    pub fn presets_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the session history, oldest first
+    pub fn history(&self) -> Result<Vec<SessionRecord>, Box<dyn Error>> {
+        Ok(self.database.borrow().sessions(&SessionQuery::all())?)
+    }
+
+    /// Adds a finished run to the session history
+    pub fn add_session(&self, record: &SessionRecord) -> Result<(), Box<dyn Error>> {
+        Ok(self.database.borrow().add_session(record)?)
+    }
+
+    /// Loads the saved presets
+    pub fn presets(&self) -> Result<PresetList, Box<dyn Error>> {
+        Ok(PresetList::from_presets(self.database.borrow().presets()?))
+    }
+
+    /// Replaces the saved presets with `presets`
+    pub fn save_presets(&self, presets: &PresetList) -> Result<(), Box<dyn Error>> {
+        Ok(self.database.borrow_mut().save_presets(presets.presets())?)
+    }
+
+    /// Gets the value of a setting, or `None` if it was never set
+    pub fn setting(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(self.database.borrow().setting(key)?)
+    }
+
+    /// Sets a setting, replacing any previous value
+    pub fn set_setting(&self, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        Ok(self.database.borrow().set_setting(key, value)?)
+    }
+
+    /// Copies the history and presets saved in flat files before the
+    /// database was used, once; sessions already in the database are skipped
+    /// so an interrupted copy can be finished on the next launch
+    fn import_flat_files(&self) -> Result<(), Box<dyn Error>> {
+        const IMPORTED: &str = "flat_files_imported";
+        if self.setting(IMPORTED)?.is_some() {
+            return Ok(());
+        }
+
+        if let Some(history) = HistoryStore::in_data_dir() {
+            let mut seen: HashSet<_> =
+                self.history()?.iter().map(|record| (record.started_at, record.ended_at)).collect();
+            for record in history.load()? {
+                if seen.insert((record.started_at, record.ended_at)) {
+                    self.add_session(&record)?;
+                }
+            }
+        }
+        if let Some(store) = PresetStore::in_data_dir() {
+            let mut presets = self.presets()?;
+            for preset in store.load()?.presets() {
+                // Presets already in the database win over the file's
+                let _ = presets.add(preset.clone());
+            }
+            self.save_presets(&presets)?;
+        }
+        self.set_setting(IMPORTED, "true")
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+impl Store {
+    /// Opens the flat files in the data folder
+    pub fn open() -> Result<Self, Box<dyn Error>> {
+        let missing = "Could not find the data folder";
+        Ok(Self {
+            history: HistoryStore::in_data_dir().ok_or(missing)?,
+            presets: PresetStore::in_data_dir().ok_or(missing)?,
+        })
+    }
+
+    /// Gets the path the session history is saved to
+    pub fn history_path(&self) -> &Path {
+        self.history.path()
+    }
+
+    /// Gets the path the presets are saved to
+    pub fn presets_path(&self) -> &Path {
+        self.presets.path()
+    }
+
+    /// Loads the session history, oldest first
+    pub fn history(&self) -> Result<Vec<SessionRecord>, Box<dyn Error>> {
+        Ok(self.history.load()?)
+    }
+
+    /// Adds a finished run to the session history
+    pub fn add_session(&self, record: &SessionRecord) -> Result<(), Box<dyn Error>> {
+        Ok(self.history.append(record)?)
+    }
+
+    /// Loads the saved presets
+    pub fn presets(&self) -> Result<PresetList, Box<dyn Error>> {
+        Ok(self.presets.load()?)
+    }
+
+    /// Replaces the saved presets with `presets`
+    pub fn save_presets(&self, presets: &PresetList) -> Result<(), Box<dyn Error>> {
+        Ok(self.presets.save(presets)?)
+    }
+
+    /// Settings are only kept with the `sqlite` feature, so there are none
+    pub fn setting(&self, _key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(None)
+    }
+
+    /// Settings are only kept with the `sqlite` feature, so this does nothing
+    pub fn set_setting(&self, _key: &str, _value: &str) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, FixedOffset, Local, LocalResult, NaiveDateTime, TimeZone, Utc};
+
+/// Source of monotonic and wall-clock time for TimerLogic
+///
+/// The engine only ever measures time through this trait, so tests can swap
+/// in a [`MockClock`] and drive the countdown deterministically.
+pub trait Clock {
+    /// Returns the current monotonic instant
+    fn now(&self) -> Instant;
+
+    /// Returns the current local wall-clock time, used for countdowns to an
+    /// absolute time; defaults to the system's local time
+    fn wall_time(&self) -> DateTime<FixedOffset> {
+        Local::now().fixed_offset()
+    }
+
+    /// Resolves a date and time on the local wall clock to the instants it
+    /// names, in the time zone of [`wall_time`](Self::wall_time); defaults to
+    /// the system's local time zone, so the offset follows daylight saving
+    /// changes on that date
+    fn local_datetime(&self, local: NaiveDateTime) -> LocalResult<DateTime<FixedOffset>> {
+        Local.from_local_datetime(&local).map(|time| time.fixed_offset())
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Rc<C> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn wall_time(&self) -> DateTime<FixedOffset> {
+        (**self).wall_time()
+    }
+
+    fn local_datetime(&self, local: NaiveDateTime) -> LocalResult<DateTime<FixedOffset>> {
+        (**self).local_datetime(local)
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn wall_time(&self) -> DateTime<FixedOffset> {
+        (**self).wall_time()
+    }
+
+    fn local_datetime(&self, local: NaiveDateTime) -> LocalResult<DateTime<FixedOffset>> {
+        (**self).local_datetime(local)
+    }
+}
+
+/// Clock backed by the operating system's monotonic and local wall clocks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Manually advanced clock for deterministic tests
+///
+/// Clones share the same virtual time, so a test can keep one handle and pass
+/// another to TimerLogic. Wall-clock time starts at 2024-01-01 09:00 UTC and
+/// moves with [`advance`](Self::advance), but can also be set on its own to
+/// simulate a change to the system clock.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    origin: Instant,
+    offset: Arc<Mutex<Duration>>,
+    wall: Arc<Mutex<DateTime<FixedOffset>>>,
+}
+
+impl MockClock {
+    /// Creates a clock frozen at the current instant
+    pub fn new() -> Self {
+        let wall = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap().fixed_offset();
+        Self {
+            origin: Instant::now(),
+            offset: Arc::new(Mutex::new(Duration::ZERO)),
+            wall: Arc::new(Mutex::new(wall)),
+        }
+    }
+
+    /// Moves virtual time forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        *self.offset.lock().unwrap() += duration;
+        *self.wall.lock().unwrap() += duration;
+    }
+
+    /// Sets the wall-clock time without moving monotonic time, like the
+    /// user or NTP changing the system clock
+    pub fn set_wall_time(&self, time: DateTime<FixedOffset>) {
+        *self.wall.lock().unwrap() = time;
+    }
+
+    /// Gets the total virtual time elapsed since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        *self.offset.lock().unwrap()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
+
+    fn wall_time(&self) -> DateTime<FixedOffset> {
+        *self.wall.lock().unwrap()
+    }
+
+    /// Resolves in the offset of the current wall-clock time, which never
+    /// changes on its own
+    fn local_datetime(&self, local: NaiveDateTime) -> LocalResult<DateTime<FixedOffset>> {
+        self.wall_time().offset().from_local_datetime(&local)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_only_moves_when_advanced() {
+        let clock = MockClock::new();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(clock.now() - start, Duration::from_millis(1500));
+        assert_eq!(clock.elapsed(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_mock_clock_clones_share_time() {
+        let clock = MockClock::new();
+        let handle = clock.clone();
+
+        handle.advance(Duration::from_secs(90));
+        assert_eq!(clock.now(), handle.now());
+        assert_eq!(clock.elapsed(), Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_mock_wall_time_can_jump() {
+        let clock = MockClock::new();
+        let start = clock.wall_time();
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(clock.wall_time() - start, chrono::Duration::seconds(60));
+
+        let now = clock.now();
+        clock.set_wall_time(start + chrono::Duration::hours(2));
+        assert_eq!(clock.now(), now);
+        assert_eq!(clock.wall_time() - start, chrono::Duration::hours(2));
+    }
+}
@@ -0,0 +1,490 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::timer::{parse_duration, validate_duration, TimerError};
+
+/// Gets the directory the configuration file is read from, following the
+/// XDG base directory spec: `$XDG_CONFIG_HOME/rust-slint-timer`, falling
+/// back to `~/.config/rust-slint-timer`
+pub fn config_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("rust-slint-timer"))
+}
+
+/// Settings read from `config.toml`
+///
+/// Every table and key is optional; missing ones keep the built-in default.
+/// Unknown keys are rejected, so a misspelled setting is reported rather
+/// than silently ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The `[timer]` table
+    pub timer: TimerDefaults,
+    /// The `[window]` table
+    pub window: WindowSettings,
+    /// The `[theme]` table
+    pub theme: ThemeSettings,
+    /// The `[sounds]` table
+    pub sounds: SoundSettings,
+    /// The `[hooks]` table
+    pub hooks: HookSettings,
+}
+
+impl Config {
+    /// Reads a configuration from TOML text, reporting the line of the first
+    /// invalid or unknown key
+    pub fn from_toml(text: &str) -> Result<Self, TimerError> {
+        toml::from_str(text).map_err(|error| TimerError::InvalidConfig {
+            line: error.span().map(|span| text[..span.start].matches('\n').count() + 1),
+            reason: error.message().to_string(),
+        })
+    }
+}
+
+/// How the timer starts out when the application opens
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimerDefaults {
+    /// Countdown duration set at start-up, e.g. `"25m"`; 5 minutes by default
+    #[serde(deserialize_with = "timer_duration")]
+    pub default_duration: Duration,
+    /// Start the countdown as soon as the window opens, and when a preset is clicked
+    pub auto_start: bool,
+    /// Keep counting past zero when a countdown completes
+    pub overtime: bool,
+    /// How often the display is refreshed, from 10ms to 1s, e.g. `"0.5s"`;
+    /// follows the display precision when not set
+    #[serde(deserialize_with = "tick_interval")]
+    pub tick_interval: Option<Duration>,
+}
+
+impl Default for TimerDefaults {
+    fn default() -> Self {
+        Self {
+            default_duration: Duration::from_secs(5 * 60),
+            auto_start: false,
+            overtime: false,
+            tick_interval: None,
+        }
+    }
+}
+
+/// Size of the main window in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowSettings {
+    /// Width, from 200 to 10000
+    #[serde(deserialize_with = "window_length")]
+    pub width: u32,
+    /// Height, from 200 to 10000
+    #[serde(deserialize_with = "window_length")]
+    pub height: u32,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self { width: 560, height: 800 }
+    }
+}
+
+/// Built-in color set a theme starts from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    /// Dark text on light panels
+    #[default]
+    Light,
+    /// Light text on dark panels
+    Dark,
+}
+
+impl ThemeName {
+    /// Gets the built-in colors of the theme
+    pub fn colors(self) -> ThemeColors {
+        match self {
+            ThemeName::Light => ThemeColors {
+                background: 0xffffff,
+                panel: 0xf8f9fa,
+                border: 0xdee2e6,
+                text: 0x495057,
+                muted: 0x6c757d,
+                accent: 0x2e86c1,
+                running: 0xffc107,
+                completed: 0x28a745,
+                alert: 0xdc3545,
+            },
+            ThemeName::Dark => ThemeColors {
+                background: 0x1e2125,
+                panel: 0x2b3035,
+                border: 0x495057,
+                text: 0xdee2e6,
+                muted: 0xadb5bd,
+                accent: 0x5dade2,
+                running: 0xffc107,
+                completed: 0x48c774,
+                alert: 0xe4606d,
+            },
+        }
+    }
+}
+
+/// Window colors as `0xRRGGBB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeColors {
+    /// Window background
+    pub background: u32,
+    /// Background of the grouped settings and lists
+    pub panel: u32,
+    /// Panel and chart outlines
+    pub border: u32,
+    /// Regular text
+    pub text: u32,
+    /// Hints and secondary text
+    pub muted: u32,
+    /// Charts and highlights
+    pub accent: u32,
+    /// A running timer
+    pub running: u32,
+    /// A completed timer
+    pub completed: u32,
+    /// Overtime and errors
+    pub alert: u32,
+}
+
+/// The `[theme]` table: a built-in theme with any of its colors replaced
+/// by `"#RRGGBB"` values
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeSettings {
+    /// Theme the colors start from, `"light"` or `"dark"`
+    pub name: ThemeName,
+    /// Replaces [`ThemeColors::background`]
+    #[serde(deserialize_with = "color")]
+    pub background: Option<u32>,
+    /// Replaces [`ThemeColors::panel`]
+    #[serde(deserialize_with = "color")]
+    pub panel: Option<u32>,
+    /// Replaces [`ThemeColors::border`]
+    #[serde(deserialize_with = "color")]
+    pub border: Option<u32>,
+    /// Replaces [`ThemeColors::text`]
+    #[serde(deserialize_with = "color")]
+    pub text: Option<u32>,
+    /// Replaces [`ThemeColors::muted`]
+    #[serde(deserialize_with = "color")]
+    pub muted: Option<u32>,
+    /// Replaces [`ThemeColors::accent`]
+    #[serde(deserialize_with = "color")]
+    pub accent: Option<u32>,
+    /// Replaces [`ThemeColors::running`]
+    #[serde(deserialize_with = "color")]
+    pub running: Option<u32>,
+    /// Replaces [`ThemeColors::completed`]
+    #[serde(deserialize_with = "color")]
+    pub completed: Option<u32>,
+    /// Replaces [`ThemeColors::alert`]
+    #[serde(deserialize_with = "color")]
+    pub alert: Option<u32>,
+}
+
+impl ThemeSettings {
+    /// Gets the colors of the named theme with the configured ones replaced
+    pub fn colors(&self) -> ThemeColors {
+        let base = self.name.colors();
+        ThemeColors {
+            background: self.background.unwrap_or(base.background),
+            panel: self.panel.unwrap_or(base.panel),
+            border: self.border.unwrap_or(base.border),
+            text: self.text.unwrap_or(base.text),
+            muted: self.muted.unwrap_or(base.muted),
+            accent: self.accent.unwrap_or(base.accent),
+            running: self.running.unwrap_or(base.running),
+            completed: self.completed.unwrap_or(base.completed),
+            alert: self.alert.unwrap_or(base.alert),
+        }
+    }
+}
+
+/// Sound files played by an external player when a timer completes or a
+/// Pomodoro or interval phase changes
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SoundSettings {
+    /// Play the sounds below; turn off to mute them without removing them
+    pub enabled: bool,
+    /// Command a sound file is passed to, `afplay` on macOS and `paplay` elsewhere
+    pub player: String,
+    /// Played when a countdown completes
+    #[serde(deserialize_with = "sound_path")]
+    pub completed: Option<PathBuf>,
+    /// Played when a Pomodoro phase or interval segment starts
+    #[serde(deserialize_with = "sound_path")]
+    pub phase: Option<PathBuf>,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        let player = if cfg!(target_os = "macos") { "afplay" } else { "paplay" };
+        Self { enabled: true, player: player.to_string(), completed: None, phase: None }
+    }
+}
+
+/// Something a hook command can run on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// The main timer started or resumed
+    Start,
+    /// The main timer was paused
+    Pause,
+    /// A countdown completed
+    Complete,
+    /// The main timer was reset
+    Reset,
+}
+
+impl HookEvent {
+    /// Gets the lowercase name hooks see in `TIMER_EVENT`, e.g. "complete"
+    pub fn key(self) -> &'static str {
+        match self {
+            HookEvent::Start => "start",
+            HookEvent::Pause => "pause",
+            HookEvent::Complete => "complete",
+            HookEvent::Reset => "reset",
+        }
+    }
+}
+
+/// Shell commands run on timer events, e.g.
+/// `on_complete = "notify-send 'Time is up'"`
+///
+/// Commands see the event in `TIMER_EVENT` and the session label in
+/// `TIMER_LABEL`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HookSettings {
+    /// Run on [`HookEvent::Start`]
+    pub on_start: Option<String>,
+    /// Run on [`HookEvent::Pause`]
+    pub on_pause: Option<String>,
+    /// Run on [`HookEvent::Complete`]
+    pub on_complete: Option<String>,
+    /// Run on [`HookEvent::Reset`]
+    pub on_reset: Option<String>,
+}
+
+impl HookSettings {
+    /// Gets the command to run on `event`, if one is configured
+    pub fn command(&self, event: HookEvent) -> Option<&str> {
+        let command = match event {
+            HookEvent::Start => &self.on_start,
+            HookEvent::Pause => &self.on_pause,
+            HookEvent::Complete => &self.on_complete,
+            HookEvent::Reset => &self.on_reset,
+        };
+        command.as_deref().filter(|command| !command.trim().is_empty())
+    }
+}
+
+fn timer_duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    let duration = parse_duration(&text).map_err(serde::de::Error::custom)?;
+    validate_duration(duration).map_err(serde::de::Error::custom)?;
+    Ok(duration)
+}
+
+fn tick_interval<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    let interval = parse_duration(&text).map_err(serde::de::Error::custom)?;
+    if !(Duration::from_millis(10)..=Duration::from_secs(1)).contains(&interval) {
+        return Err(serde::de::Error::custom("tick interval must be between 0.01s and 1s"));
+    }
+    Ok(Some(interval))
+}
+
+fn window_length<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let length = u32::deserialize(deserializer)?;
+    if !(200..=10_000).contains(&length) {
+        return Err(serde::de::Error::custom(format!("window size must be between 200 and 10000, got {}", length)));
+    }
+    Ok(length)
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    match text.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+            Ok(u32::from_str_radix(hex, 16).ok())
+        }
+        _ => Err(serde::de::Error::custom(format!("invalid color \"{}\", expected \"#RRGGBB\"", text))),
+    }
+}
+
+/// Reads a path, expanding a leading `~/` to the home directory
+fn sound_path<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<PathBuf>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    match (text.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Ok(Some(PathBuf::from(home).join(rest))),
+        _ => Ok(Some(PathBuf::from(text))),
+    }
+}
+
+/// The configuration file, remembering when it was last read so changes
+/// can be picked up while the application runs
+pub struct ConfigFile {
+    path: PathBuf,
+    // Modification time and length when last read, `None` while the file is missing
+    stamp: Option<(SystemTime, u64)>,
+}
+
+impl ConfigFile {
+    /// Creates a handle for the configuration file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), stamp: None }
+    }
+
+    /// Creates a handle for the file `config.toml` in the application's [`config_dir`]
+    pub fn in_config_dir() -> Option<Self> {
+        Some(Self::new(config_dir()?.join("config.toml")))
+    }
+
+    /// Gets the path of the configuration file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the configuration; a missing file is the default configuration
+    pub fn load(&mut self) -> io::Result<Config> {
+        self.stamp = self.current_stamp();
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(error) => return Err(error),
+        };
+        Config::from_toml(&text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// Reads the configuration again if the file was changed, created or
+    /// removed since it was last read, or returns `None` if it was not
+    pub fn reload_if_changed(&mut self) -> Option<io::Result<Config>> {
+        (self.current_stamp() != self.stamp).then(|| self.load())
+    }
+
+    fn current_stamp(&self) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(&self.path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_file_is_default() {
+        let config = Config::from_toml("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.timer.default_duration, Duration::from_secs(300));
+        assert_eq!(config.window, WindowSettings { width: 560, height: 800 });
+        assert_eq!(config.theme.colors(), ThemeName::Light.colors());
+    }
+
+    #[test]
+    fn test_full_config() {
+        let config = Config::from_toml(
+            r##"
+            [timer]
+            default_duration = "25m"
+            auto_start = true
+            overtime = true
+            tick_interval = "0.5s"
+
+            [window]
+            width = 480
+            height = 900
+
+            [theme]
+            name = "dark"
+            accent = "#FF8800"
+
+            [sounds]
+            player = "aplay"
+            completed = "/usr/share/sounds/done.wav"
+
+            [hooks]
+            on_complete = "notify-send 'Time is up'"
+            on_pause = " "
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(config.timer.default_duration, Duration::from_secs(25 * 60));
+        assert!(config.timer.auto_start && config.timer.overtime);
+        assert_eq!(config.timer.tick_interval, Some(Duration::from_millis(500)));
+        assert_eq!(config.window, WindowSettings { width: 480, height: 900 });
+        let colors = config.theme.colors();
+        assert_eq!(colors.accent, 0xff8800);
+        assert_eq!(colors.panel, ThemeName::Dark.colors().panel);
+        assert!(config.sounds.enabled);
+        assert_eq!(config.sounds.completed, Some(PathBuf::from("/usr/share/sounds/done.wav")));
+        assert_eq!(config.hooks.command(HookEvent::Complete), Some("notify-send 'Time is up'"));
+        assert_eq!(config.hooks.command(HookEvent::Pause), None);
+        assert_eq!(config.hooks.command(HookEvent::Start), None);
+    }
+
+    #[test]
+    fn test_errors_name_the_line_and_key() {
+        let error = Config::from_toml("[timer]\nauto_start = true\ndefault_duraton = \"5m\"\n").unwrap_err();
+        let TimerError::InvalidConfig { line, reason } = &error else {
+            panic!("unexpected error {:?}", error);
+        };
+        assert_eq!(*line, Some(3));
+        assert!(reason.contains("unknown field `default_duraton`"), "{}", reason);
+        assert!(error.to_string().starts_with("Configuration is invalid at line 3: "));
+
+        let error = Config::from_toml("[colours]\n").unwrap_err();
+        assert!(matches!(error, TimerError::InvalidConfig { line: Some(1), .. }), "{:?}", error);
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let reason = |text: &str| match Config::from_toml(text) {
+            Err(TimerError::InvalidConfig { reason, .. }) => reason,
+            other => panic!("unexpected result {:?}", other),
+        };
+        assert_eq!(reason("timer.default_duration = \"0\""), "Timer duration cannot be zero");
+        assert_eq!(reason("timer.tick_interval = \"5s\""), "tick interval must be between 0.01s and 1s");
+        assert_eq!(reason("window.width = 50"), "window size must be between 200 and 10000, got 50");
+        assert_eq!(reason("theme.text = \"red\""), "invalid color \"red\", expected \"#RRGGBB\"");
+        assert!(reason("theme.name = \"blue\"").contains("unknown variant `blue`"));
+        assert!(reason("timer.overtime = \"yes\"").contains("expected a boolean"));
+    }
+
+    #[test]
+    fn test_reload_only_when_changed() {
+        let dir = env::temp_dir().join(format!("rust-slint-timer-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut file = ConfigFile::new(dir.join("config.toml"));
+        assert_eq!(file.load().unwrap(), Config::default());
+        assert!(file.reload_if_changed().is_none());
+
+        fs::write(file.path(), "[timer]\novertime = true\n").unwrap();
+        assert!(file.reload_if_changed().unwrap().unwrap().timer.overtime);
+        assert!(file.reload_if_changed().is_none());
+
+        fs::write(file.path(), "[timer]\novertime = maybe\n").unwrap();
+        assert_eq!(file.reload_if_changed().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_file(file.path()).unwrap();
+        assert_eq!(file.reload_if_changed().unwrap().unwrap(), Config::default());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
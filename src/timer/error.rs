@@ -0,0 +1,262 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::timer::{PomodoroPhase, MAX_DAYS};
+
+/// Errors returned when configuring timers
+///
+/// New variants may be added as the engine grows, so matches outside this
+/// crate need a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimerError {
+    /// Days value above [`MAX_DAYS`]
+    DaysOutOfRange(u32),
+    /// Hours value above 23
+    HoursOutOfRange(u32),
+    /// Minutes value above 59
+    MinutesOutOfRange(u32),
+    /// Seconds value above 59
+    SecondsOutOfRange(u32),
+    /// Hours, minutes and seconds are all zero
+    ZeroDuration,
+    /// A duration was entered as an empty string
+    EmptyDurationInput,
+    /// Text that could not be read as a duration
+    InvalidDurationInput(String),
+    /// A duration used a unit other than days, hours, minutes or seconds
+    UnknownDurationUnit(String),
+    /// Text that could not be read as a time of day
+    InvalidTimeOfDay(String),
+    /// A countdown target that is not in the future
+    TargetInPast(DateTime<FixedOffset>),
+    /// A JSON document that could not be read or written
+    InvalidJson(String),
+    /// A JSON document written by an unsupported version of the schema
+    UnsupportedJsonVersion(u32),
+    /// A saved state file that could not be read back
+    InvalidSavedState {
+        /// 1-based line the problem was found on
+        line: usize,
+        /// What is wrong with the line
+        reason: String,
+    },
+    /// A session history file line that could not be read back
+    InvalidHistoryRecord {
+        /// 1-based line the problem was found on
+        line: usize,
+        /// What is wrong with the line
+        reason: String,
+    },
+    /// The timer database could not be read or written
+    Storage(StorageError),
+    /// A timer database written by a newer version of the application
+    UnsupportedSchemaVersion(u32),
+    /// An export file name whose extension is not a known format
+    UnknownExportFormat(String),
+    /// An import file name whose extension is not a known format
+    UnknownImportFormat(String),
+    /// A file format this build was compiled without, e.g. JSON without the
+    /// `serde` feature
+    UnsupportedFormat(String),
+    /// A field of an imported entry that could not be read
+    InvalidImportField {
+        /// Name of the field, e.g. "started_at"
+        field: String,
+        /// The value that could not be read
+        value: String,
+    },
+    /// An imported entry that was skipped because it is invalid
+    InvalidImportEntry {
+        /// 1-based line of a CSV file, or position in a JSON array
+        entry: usize,
+        /// Why the entry is invalid
+        source: Box<TimerError>,
+    },
+    /// A configuration file that could not be read
+    InvalidConfig {
+        /// 1-based line the problem was found on, if known
+        line: Option<usize>,
+        /// What is wrong with the file
+        reason: String,
+    },
+    /// A preset name that is empty or only whitespace
+    EmptyPresetName,
+    /// A preset name that another preset already has, ignoring case
+    DuplicatePresetName(String),
+    /// A preset that does not exist
+    UnknownPreset(String),
+    /// A Pomodoro phase length is not a valid timer duration
+    InvalidPhaseLength {
+        /// Phase with the invalid length
+        phase: PomodoroPhase,
+        /// Why the length is invalid
+        source: Box<TimerError>,
+    },
+    /// The Pomodoro long break interval is zero
+    ZeroLongBreakInterval,
+    /// An interval program has no segments
+    EmptySequence,
+    /// An interval program segment is not a valid timer duration
+    InvalidSegment {
+        /// Label of the invalid segment
+        label: String,
+        /// Why the segment is invalid
+        source: Box<TimerError>,
+    },
+}
+
+impl fmt::Display for TimerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerError::DaysOutOfRange(days) => {
+                write!(f, "Days must be between 0 and {}, got {}", MAX_DAYS, days)
+            }
+            TimerError::HoursOutOfRange(hours) => {
+                write!(f, "Hours must be between 0 and 23, got {}", hours)
+            }
+            TimerError::MinutesOutOfRange(minutes) => {
+                write!(f, "Minutes must be between 0 and 59, got {}", minutes)
+            }
+            TimerError::SecondsOutOfRange(seconds) => {
+                write!(f, "Seconds must be between 0 and 59, got {}", seconds)
+            }
+            TimerError::ZeroDuration => write!(f, "Timer duration cannot be zero"),
+            TimerError::EmptyDurationInput => write!(f, "Enter a duration, e.g. 1h30m, 90s or 1:30"),
+            TimerError::InvalidDurationInput(input) => {
+                write!(f, "Cannot read \"{}\" as a duration, try e.g. 1h30m, 90s or 1:30", input)
+            }
+            TimerError::UnknownDurationUnit(unit) => {
+                write!(f, "Unknown duration unit \"{}\", use d, h, m or s", unit)
+            }
+            TimerError::InvalidTimeOfDay(input) => {
+                write!(f, "Cannot read \"{}\" as a time of day, try e.g. 14:30 or 2:30pm", input)
+            }
+            TimerError::TargetInPast(target) => {
+                write!(f, "Target time {} has already passed", target.format("%Y-%m-%d %H:%M:%S"))
+            }
+            TimerError::InvalidJson(reason) => write!(f, "Invalid timer JSON: {}", reason),
+            TimerError::UnsupportedJsonVersion(version) => {
+                write!(f, "Unsupported timer JSON version {}", version)
+            }
+            TimerError::InvalidSavedState { line, reason } => {
+                write!(f, "Saved timer state is invalid at line {}: {}", line, reason)
+            }
+            TimerError::InvalidHistoryRecord { line, reason } => {
+                write!(f, "Session history is invalid at line {}: {}", line, reason)
+            }
+            TimerError::Storage(error) => write!(f, "Timer database error: {}", error),
+            TimerError::UnsupportedSchemaVersion(version) => {
+                write!(f, "Timer database schema version {} is newer than this version supports", version)
+            }
+            TimerError::UnknownExportFormat(path) => {
+                write!(f, "Cannot tell the export format of \"{}\", use .csv, .json or .ics", path)
+            }
+            TimerError::UnknownImportFormat(path) => {
+                write!(f, "Cannot tell the import format of \"{}\", use .csv or .json", path)
+            }
+            TimerError::UnsupportedFormat(format) => {
+                write!(f, "{} files are not supported by this build of the timer", format)
+            }
+            TimerError::InvalidImportField { field, value } => write!(f, "Invalid {} \"{}\"", field, value),
+            TimerError::InvalidImportEntry { entry, source } => write!(f, "Entry {}: {}", entry, source),
+            TimerError::InvalidConfig { line: Some(line), reason } => {
+                write!(f, "Configuration is invalid at line {}: {}", line, reason)
+            }
+            TimerError::InvalidConfig { line: None, reason } => write!(f, "Configuration is invalid: {}", reason),
+            TimerError::EmptyPresetName => write!(f, "Preset name cannot be empty"),
+            TimerError::DuplicatePresetName(name) => write!(f, "A preset named \"{}\" already exists", name),
+            TimerError::UnknownPreset(name) => write!(f, "No preset named \"{}\"", name),
+            TimerError::InvalidPhaseLength { phase, source } => {
+                write!(f, "{} length is invalid: {}", phase.label(), source)
+            }
+            TimerError::ZeroLongBreakInterval => write!(f, "Long break interval must be at least 1"),
+            TimerError::EmptySequence => write!(f, "Sequence must contain at least one segment"),
+            TimerError::InvalidSegment { label, source } => {
+                write!(f, "Segment \"{}\" is invalid: {}", label, source)
+            }
+        }
+    }
+}
+
+impl Error for TimerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TimerError::InvalidPhaseLength { source, .. }
+            | TimerError::InvalidSegment { source, .. }
+            | TimerError::InvalidImportEntry { source, .. } => {
+                Some(source.as_ref())
+            }
+            TimerError::Storage(error) => Some(error.0.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// The underlying error of a [`TimerError::Storage`], e.g. from SQLite
+///
+/// Shared rather than owned so that [`TimerError`] stays `Clone`; two storage
+/// errors are equal when they have the same message.
+#[derive(Debug, Clone)]
+pub struct StorageError(Arc<dyn Error + Send + Sync>);
+
+impl StorageError {
+    /// Wraps an error, or a message such as `"negative duration"`
+    pub fn new(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self(Arc::from(error.into()))
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for StorageError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl Eq for StorageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_messages() {
+        assert_eq!(TimerError::HoursOutOfRange(24).to_string(), "Hours must be between 0 and 23, got 24");
+        assert_eq!(TimerError::DaysOutOfRange(120).to_string(), "Days must be between 0 and 99, got 120");
+        assert_eq!(TimerError::ZeroDuration.to_string(), "Timer duration cannot be zero");
+
+        let error = TimerError::InvalidSegment {
+            label: "Work".to_string(),
+            source: Box::new(TimerError::SecondsOutOfRange(75)),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Segment \"Work\" is invalid: Seconds must be between 0 and 59, got 75"
+        );
+    }
+
+    #[test]
+    fn test_source_chain() {
+        let error = TimerError::InvalidPhaseLength {
+            phase: PomodoroPhase::ShortBreak,
+            source: Box::new(TimerError::ZeroDuration),
+        };
+        assert_eq!(error.to_string(), "Short Break length is invalid: Timer duration cannot be zero");
+        assert_eq!(error.source().unwrap().to_string(), "Timer duration cannot be zero");
+        assert!(TimerError::ZeroDuration.source().is_none());
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "read-only file system");
+        let error = TimerError::Storage(StorageError::new(io_error));
+        assert_eq!(error.to_string(), "Timer database error: read-only file system");
+        assert!(error.source().unwrap().downcast_ref::<std::io::Error>().is_some());
+    }
+}
@@ -0,0 +1,299 @@
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, Utc};
+
+use crate::timer::{DisplayPrecision, Preset, SessionRecord, TimerError, TimerMode};
+
+/// File format session history can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row, for spreadsheets
+    Csv,
+    /// The records as versioned JSON, see [`to_json`](crate::timer::to_json);
+    /// needs the `serde` feature
+    Json,
+    /// An iCalendar file with one VEVENT per session, for calendar apps
+    Ics,
+}
+
+impl ExportFormat {
+    /// Picks the format from the extension of `path`, e.g. `history.ics`
+    pub fn from_path(path: &Path) -> Result<Self, TimerError> {
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "ics" => Ok(ExportFormat::Ics),
+            _ => Err(TimerError::UnknownExportFormat(path.display().to_string())),
+        }
+    }
+
+    /// Gets the file extension for the format, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ics => "ics",
+        }
+    }
+}
+
+/// Exports `records` in `format`; `now` is the time the export was made,
+/// which .ics files record as each event's DTSTAMP
+///
+/// JSON fails with [`TimerError::UnsupportedFormat`] without the `serde` feature.
+pub fn export_history(
+    records: &[SessionRecord],
+    format: ExportFormat,
+    now: DateTime<FixedOffset>,
+) -> Result<String, TimerError> {
+    match format {
+        ExportFormat::Csv => Ok(export_csv(records)),
+        #[cfg(feature = "serde")]
+        ExportFormat::Json => crate::timer::to_json(&records),
+        #[cfg(not(feature = "serde"))]
+        ExportFormat::Json => Err(TimerError::UnsupportedFormat("JSON".to_string())),
+        ExportFormat::Ics => Ok(export_ics(records, now)),
+    }
+}
+
+/// Exports `records` as CSV, one row per session
+///
+/// Times are RFC 3339 in the offset they were recorded in, and durations are
+/// seconds with millisecond precision, so the file sorts and sums easily.
+pub fn export_csv(records: &[SessionRecord]) -> String {
+    let mut csv = String::from("started_at,ended_at,outcome,mode,configured_seconds,elapsed_seconds,label\r\n");
+    for record in records {
+        let fields = [
+            record.started_at.to_rfc3339(),
+            record.ended_at.to_rfc3339(),
+            record.outcome.key().to_string(),
+            record.mode.key().to_string(),
+            seconds(record.configured),
+            seconds(record.elapsed),
+            csv_field(record.label.as_deref().unwrap_or("")),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Exports `presets` as CSV with the columns `name,duration`, which
+/// [`import_presets`](crate::timer::import_presets) reads back
+///
+/// Durations are written as `HH:MM:SS`, or `D:HH:MM:SS` from a day up.
+pub fn export_presets_csv(presets: &[Preset]) -> String {
+    let mut csv = String::from("name,duration\r\n");
+    for preset in presets {
+        csv.push_str(&csv_field(&preset.name));
+        csv.push(',');
+        csv.push_str(&DisplayPrecision::Seconds.format(preset.duration));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Exports `records` as an iCalendar (RFC 5545) file with one VEVENT per session
+///
+/// Each event is named after the session's label and spans the time from
+/// start to end, including any pauses. UIDs are derived from the session
+/// times, so importing the same sessions twice updates rather than
+/// duplicates them.
+pub fn export_ics(records: &[SessionRecord], now: DateTime<FixedOffset>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//rust-slint-timer//Session history//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for record in records {
+        let precision = DisplayPrecision::Seconds;
+        let elapsed = precision.format_elapsed(record.elapsed);
+        let mut description = format!("{} after {}", record.outcome.label(), elapsed);
+        if record.mode == TimerMode::Countdown {
+            description.push_str(&format!(" of {}", precision.format_elapsed(record.configured)));
+        }
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!(
+                "UID:{}-{}@rust-slint-timer",
+                ics_time(record.started_at),
+                ics_time(record.ended_at)
+            ),
+            format!("DTSTAMP:{}", ics_time(now)),
+            format!("DTSTART:{}", ics_time(record.started_at)),
+            format!("DTEND:{}", ics_time(record.ended_at)),
+            format!("SUMMARY:{}", ics_text(record.label.as_deref().unwrap_or("Timer session"))),
+            format!("DESCRIPTION:{}", ics_text(&description)),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in lines {
+        ics.push_str(&fold_ics_line(&line));
+        ics.push_str("\r\n");
+    }
+    ics
+}
+
+/// Formats whole seconds with a three-digit millisecond fraction, e.g. "83.450"
+fn seconds(duration: Duration) -> String {
+    format!("{}.{:03}", duration.as_secs(), duration.subsec_millis())
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats a time as an iCalendar UTC date-time, e.g. "20240101T090000Z"
+fn ics_time(time: DateTime<FixedOffset>) -> String {
+    time.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes an iCalendar TEXT value
+fn ics_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Folds a content line into chunks of at most 75 octets, continuing each
+/// chunk on a new line that starts with a space, without splitting a
+/// UTF-8 character
+fn fold_ics_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+    for character in line.chars() {
+        if width + character.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(character);
+        width += character.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::{Clock, MockClock, SessionOutcome};
+    use chrono::TimeDelta;
+    use std::path::PathBuf;
+
+    /// Fixed sessions the golden files in `tests/golden` were written from
+    fn golden_records() -> Vec<SessionRecord> {
+        let monday = MockClock::new().wall_time();
+        let berlin = FixedOffset::east_opt(3600).unwrap();
+        vec![
+            SessionRecord {
+                mode: TimerMode::Countdown,
+                configured: Duration::from_secs(25 * 60),
+                elapsed: Duration::from_secs(25 * 60),
+                started_at: monday,
+                ended_at: monday + TimeDelta::minutes(31),
+                outcome: SessionOutcome::Completed,
+                label: Some("Write report, \"final\" draft".to_string()),
+            },
+            SessionRecord {
+                mode: TimerMode::Stopwatch,
+                configured: Duration::ZERO,
+                elapsed: Duration::from_millis(83_450),
+                started_at: (monday + TimeDelta::hours(5)).with_timezone(&berlin),
+                ended_at: (monday + TimeDelta::hours(5) + TimeDelta::seconds(84)).with_timezone(&berlin),
+                outcome: SessionOutcome::Reset,
+                label: None,
+            },
+            SessionRecord {
+                mode: TimerMode::Countdown,
+                configured: Duration::from_secs(2 * 3600),
+                elapsed: Duration::from_secs(600),
+                started_at: monday + TimeDelta::days(1),
+                ended_at: monday + TimeDelta::days(1) + TimeDelta::minutes(10),
+                outcome: SessionOutcome::Abandoned,
+                label: Some("Client call; notes\nand a label long enough to need folding in the ics file".to_string()),
+            },
+        ]
+    }
+
+    fn exported_at() -> DateTime<FixedOffset> {
+        MockClock::new().wall_time() + TimeDelta::days(7)
+    }
+
+    #[test]
+    fn test_csv_golden() {
+        assert_eq!(export_csv(&golden_records()), include_str!("../../tests/golden/history.csv"));
+    }
+
+    #[test]
+    fn test_ics_golden() {
+        let ics = export_ics(&golden_records(), exported_at());
+        assert_eq!(ics, include_str!("../../tests/golden/history.ics"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_golden() {
+        let json = export_history(&golden_records(), ExportFormat::Json, exported_at()).unwrap();
+        assert_eq!(json, include_str!("../../tests/golden/history.json").trim_end());
+        assert_eq!(crate::timer::from_json::<Vec<SessionRecord>>(&json).unwrap(), golden_records());
+    }
+
+    #[cfg(not(feature = "serde"))]
+    #[test]
+    fn test_json_needs_serde() {
+        assert_eq!(
+            export_history(&golden_records(), ExportFormat::Json, exported_at()),
+            Err(TimerError::UnsupportedFormat("JSON".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_presets_csv() {
+        let presets = [
+            Preset::new("Tea, green", Duration::from_secs(180)).unwrap(),
+            Preset::new("Bread", Duration::from_secs(26 * 3600)).unwrap(),
+        ];
+        assert_eq!(export_presets_csv(&presets), "name,duration\r\n\"Tea, green\",00:03:00\r\nBread,1:02:00:00\r\n");
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ExportFormat::from_path(&PathBuf::from("out/history.CSV")), Ok(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_path(&PathBuf::from("history.ics")), Ok(ExportFormat::Ics));
+        assert_eq!(ExportFormat::from_path(&PathBuf::from("history.json")), Ok(ExportFormat::Json));
+        assert_eq!(
+            ExportFormat::from_path(&PathBuf::from("history.xlsx")),
+            Err(TimerError::UnknownExportFormat("history.xlsx".to_string()))
+        );
+        assert_eq!(ExportFormat::Ics.extension(), "ics");
+    }
+
+    #[test]
+    fn test_fold_keeps_characters_whole() {
+        let line = format!("SUMMARY:{}", "é".repeat(60));
+        let folded = fold_ics_line(&line);
+        assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
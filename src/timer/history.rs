@@ -0,0 +1,304 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::timer::{data_dir, TimerError, TimerMode};
+
+/// How a timer run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SessionOutcome {
+    /// The countdown reached zero
+    Completed,
+    /// The run was reset before it completed
+    Reset,
+    /// The run was replaced by new settings before it completed
+    Abandoned,
+}
+
+impl SessionOutcome {
+    /// Gets the name shown in the UI, e.g. "Completed"
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionOutcome::Completed => "Completed",
+            SessionOutcome::Reset => "Reset",
+            SessionOutcome::Abandoned => "Abandoned",
+        }
+    }
+
+    /// Gets the name the outcome is stored under, e.g. "completed"
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            SessionOutcome::Completed => "completed",
+            SessionOutcome::Reset => "reset",
+            SessionOutcome::Abandoned => "abandoned",
+        }
+    }
+
+    /// Gets the outcome stored under `key` by [`key`](Self::key)
+    pub(crate) fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "completed" => Some(SessionOutcome::Completed),
+            "reset" => Some(SessionOutcome::Reset),
+            "abandoned" => Some(SessionOutcome::Abandoned),
+            _ => None,
+        }
+    }
+}
+
+/// A finished timer run, as reported by
+/// [`TimerLogic::set_session_callback`](crate::timer::TimerLogic::set_session_callback)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionRecord {
+    /// Whether the run was a countdown or a stopwatch
+    pub mode: TimerMode,
+    /// Configured countdown duration; zero for a stopwatch
+    pub configured: Duration,
+    /// Running time of the run, excluding time spent paused and including
+    /// any overtime
+    pub elapsed: Duration,
+    /// Wall-clock time the run was first started
+    pub started_at: DateTime<FixedOffset>,
+    /// Wall-clock time the run ended
+    pub ended_at: DateTime<FixedOffset>,
+    /// How the run ended
+    pub outcome: SessionOutcome,
+    /// What the run was for, e.g. "Write report"
+    pub label: Option<String>,
+}
+
+/// Encodes a record as one tab-separated line of a history file, without
+/// the trailing newline
+///
+/// Tabs and line breaks in the label are replaced with spaces.
+pub fn encode_record(record: &SessionRecord) -> String {
+    let label = record.label.as_deref().unwrap_or("").replace(['\t', '\n', '\r'], " ");
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        record.started_at.to_rfc3339(),
+        record.ended_at.to_rfc3339(),
+        record.outcome.key(),
+        record.mode.key(),
+        record.configured.as_nanos(),
+        record.elapsed.as_nanos(),
+        label,
+    )
+}
+
+/// Decodes a line written by [`encode_record`]; `line_number` is only used
+/// in the error
+pub fn decode_record(line: &str, line_number: usize) -> Result<SessionRecord, TimerError> {
+    let invalid = |reason: &str| TimerError::InvalidHistoryRecord {
+        line: line_number,
+        reason: reason.to_string(),
+    };
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [started_at, ended_at, outcome, mode, configured, elapsed, label] = fields[..] else {
+        return Err(invalid("expected 7 tab-separated fields"));
+    };
+    let time = |value: &str| DateTime::parse_from_rfc3339(value).map_err(|_| invalid("expected an RFC 3339 time"));
+    let nanos = |value: &str| {
+        value
+            .parse::<u64>()
+            .map(Duration::from_nanos)
+            .map_err(|_| invalid("expected a number of nanoseconds"))
+    };
+
+    Ok(SessionRecord {
+        mode: TimerMode::from_key(mode).ok_or_else(|| invalid("expected countdown or stopwatch"))?,
+        configured: nanos(configured)?,
+        elapsed: nanos(elapsed)?,
+        started_at: time(started_at)?,
+        ended_at: time(ended_at)?,
+        outcome: SessionOutcome::from_key(outcome).ok_or_else(|| invalid("expected completed, reset or abandoned"))?,
+        label: (!label.is_empty()).then(|| label.to_string()),
+    })
+}
+
+/// Append-only file of finished runs, one [`encode_record`] line each
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    /// Creates a store for the history file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Creates a store for the file `history` in the application's [`data_dir`]
+    pub fn in_data_dir() -> Option<Self> {
+        Some(Self::new(data_dir()?.join("history")))
+    }
+
+    /// Gets the path of the history file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a record to the end of the file
+    pub fn append(&self, record: &SessionRecord) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", encode_record(record))
+    }
+
+    /// Loads every record, oldest first; a missing file is an empty history
+    pub fn load(&self) -> io::Result<Vec<SessionRecord>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| decode_record(line, index + 1))
+            .collect::<Result<_, _>>()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::{Clock, MockClock, TimerLogic};
+    use chrono::TimeDelta;
+    use std::sync::{Arc, Mutex};
+
+    fn recording_timer(clock: &MockClock) -> (TimerLogic, Arc<Mutex<Vec<SessionRecord>>>) {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        let sink = records.clone();
+        timer.set_session_callback(move |record| sink.lock().unwrap().push(record));
+        (timer, records)
+    }
+
+    #[test]
+    fn test_completed_run_is_recorded() {
+        let clock = MockClock::new();
+        let start = clock.wall_time();
+        let (mut timer, records) = recording_timer(&clock);
+        timer.set_label(Some("Write report".to_string()));
+        timer.set_time(0, 0, 30).unwrap();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(10));
+        timer.pause_timer();
+        clock.advance(Duration::from_secs(60));
+        timer.start_timer();
+        clock.advance(Duration::from_secs(20));
+        assert!(timer.tick());
+
+        let records = records.lock().unwrap();
+        assert_eq!(
+            *records,
+            [SessionRecord {
+                mode: TimerMode::Countdown,
+                configured: Duration::from_secs(30),
+                elapsed: Duration::from_secs(30),
+                started_at: start,
+                ended_at: start + TimeDelta::seconds(90),
+                outcome: SessionOutcome::Completed,
+                label: Some("Write report".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reset_and_abandoned_runs_are_recorded() {
+        let clock = MockClock::new();
+        let (mut timer, records) = recording_timer(&clock);
+        timer.set_time(0, 5, 0).unwrap();
+
+        // Resetting a run that never started records nothing
+        timer.reset_timer();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(60));
+        timer.reset_timer();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(30));
+        timer.set_time(0, 1, 0).unwrap();
+
+        let outcomes: Vec<_> = records.lock().unwrap().iter().map(|record| (record.outcome, record.elapsed)).collect();
+        assert_eq!(
+            outcomes,
+            [
+                (SessionOutcome::Reset, Duration::from_secs(60)),
+                (SessionOutcome::Abandoned, Duration::from_secs(30)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overtime_run_is_recorded_when_reset() {
+        let clock = MockClock::new();
+        let (mut timer, records) = recording_timer(&clock);
+        timer.set_overtime(true);
+        timer.set_time(0, 0, 10).unwrap();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(15));
+        assert!(timer.tick());
+        assert!(records.lock().unwrap().is_empty());
+
+        clock.advance(Duration::from_secs(5));
+        timer.reset_timer();
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].outcome, SessionOutcome::Completed);
+        assert_eq!(records[0].elapsed, Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let clock = MockClock::new();
+        let record = SessionRecord {
+            mode: TimerMode::Stopwatch,
+            configured: Duration::ZERO,
+            elapsed: Duration::from_millis(83_450),
+            started_at: clock.wall_time(),
+            ended_at: clock.wall_time() + TimeDelta::minutes(2),
+            outcome: SessionOutcome::Reset,
+            label: Some("Tabs\tand\nnewlines".to_string()),
+        };
+        let decoded = decode_record(&encode_record(&record), 1).unwrap();
+        assert_eq!(decoded.label.as_deref(), Some("Tabs and newlines"));
+        assert_eq!(decoded, SessionRecord { label: decoded.label.clone(), ..record.clone() });
+
+        let unlabeled = SessionRecord { label: None, ..record };
+        assert_eq!(decode_record(&encode_record(&unlabeled), 1).unwrap(), unlabeled);
+
+        assert_eq!(
+            decode_record("2024-01-01T09:00:00+00:00\tnope", 3).unwrap_err(),
+            TimerError::InvalidHistoryRecord { line: 3, reason: "expected 7 tab-separated fields".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_store_appends_and_loads() {
+        let dir = std::env::temp_dir().join(format!("rust-slint-timer-history-{}", std::process::id()));
+        let store = HistoryStore::new(dir.join("history"));
+        assert!(store.load().unwrap().is_empty());
+
+        let clock = MockClock::new();
+        let record = SessionRecord {
+            mode: TimerMode::Countdown,
+            configured: Duration::from_secs(1500),
+            elapsed: Duration::from_secs(1500),
+            started_at: clock.wall_time(),
+            ended_at: clock.wall_time() + TimeDelta::minutes(25),
+            outcome: SessionOutcome::Completed,
+            label: None,
+        };
+        store.append(&record).unwrap();
+        store.append(&record).unwrap();
+        assert_eq!(store.load().unwrap(), [record.clone(), record]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
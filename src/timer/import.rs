@@ -0,0 +1,435 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::DateTime;
+
+use crate::timer::preset::name_key;
+use crate::timer::{
+    parse_duration, validate_duration, Preset, SessionOutcome, SessionRecord, TimerError, TimerMode,
+};
+
+/// File format presets and session history can be imported from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// Versioned JSON, as written by [`to_json`](crate::timer::to_json);
+    /// needs the `serde` feature
+    Json,
+}
+
+impl ImportFormat {
+    /// Picks the format from the extension of `path`, e.g. `presets.csv`
+    pub fn from_path(path: &Path) -> Result<Self, TimerError> {
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ImportFormat::Csv),
+            "json" => Ok(ImportFormat::Json),
+            _ => Err(TimerError::UnknownImportFormat(path.display().to_string())),
+        }
+    }
+}
+
+/// Entries numbered as in [`ImportReport`], each read or with the reason it couldn't be
+type Entries<T> = Vec<(usize, Result<T, TimerError>)>;
+
+/// Outcome of an import: the new entries, and what was left out
+///
+/// Entries are numbered by line in CSV files, so the first row after the
+/// header is entry 2, and by position in the array in JSON files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportReport<T> {
+    /// Valid entries that were not already present, in file order
+    pub imported: Vec<T>,
+    /// Number of valid entries skipped as duplicates
+    pub duplicates: usize,
+    /// One [`TimerError::InvalidImportEntry`] per invalid entry, or a single
+    /// error if the file could not be read at all
+    pub errors: Vec<TimerError>,
+}
+
+impl<T> ImportReport<T> {
+    fn failed(error: TimerError) -> Self {
+        Self { imported: Vec::new(), duplicates: 0, errors: vec![error] }
+    }
+
+    /// Keeps the valid entries that `is_new` accepts, counting the rest as duplicates
+    fn collect(entries: Entries<T>, mut is_new: impl FnMut(&T) -> bool) -> Self {
+        let mut report = Self { imported: Vec::new(), duplicates: 0, errors: Vec::new() };
+        for (entry, result) in entries {
+            match result {
+                Ok(value) if is_new(&value) => report.imported.push(value),
+                Ok(_) => report.duplicates += 1,
+                Err(error) => report.errors.push(TimerError::InvalidImportEntry { entry, source: Box::new(error) }),
+            }
+        }
+        report
+    }
+}
+
+/// Imports presets, skipping any whose name matches an existing preset or
+/// an earlier entry, ignoring case
+///
+/// CSV files have a `name,duration` header, and durations in any form
+/// [`parse_duration`] reads, e.g. `3m` or `0:15:00`. Every duration is
+/// checked with [`validate_duration`].
+pub fn import_presets(text: &str, format: ImportFormat, existing: &[Preset]) -> ImportReport<Preset> {
+    let entries = match format {
+        ImportFormat::Csv => match csv_entries(text, &["name", "duration"]) {
+            Ok(rows) => rows
+                .into_iter()
+                .map(|(entry, fields)| (entry, fields.and_then(|fields| preset_from_csv(&fields))))
+                .collect(),
+            Err(error) => return ImportReport::failed(error),
+        },
+        #[cfg(feature = "serde")]
+        ImportFormat::Json => match json_entries::<Preset>(text) {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|(entry, preset)| (entry, preset.and_then(|preset| Preset::new(preset.name, preset.duration))))
+                .collect(),
+            Err(error) => return ImportReport::failed(error),
+        },
+        #[cfg(not(feature = "serde"))]
+        ImportFormat::Json => return ImportReport::failed(TimerError::UnsupportedFormat("JSON".to_string())),
+    };
+
+    let mut names: HashSet<String> = existing.iter().map(|preset| name_key(&preset.name)).collect();
+    ImportReport::collect(entries, |preset| names.insert(name_key(&preset.name)))
+}
+
+/// Imports session history, skipping sessions that match an existing
+/// session or an earlier entry by start and end time
+///
+/// CSV files use the columns written by
+/// [`export_csv`](crate::timer::export_csv), in any order. The configured
+/// duration of every countdown is checked with [`validate_duration`].
+pub fn import_sessions(text: &str, format: ImportFormat, existing: &[SessionRecord]) -> ImportReport<SessionRecord> {
+    let entries = match format {
+        ImportFormat::Csv => {
+            let columns = ["started_at", "ended_at", "outcome", "mode", "configured_seconds", "elapsed_seconds", "label"];
+            match csv_entries(text, &columns) {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|(entry, fields)| {
+                        let record = fields.and_then(|fields| session_from_csv(&fields));
+                        (entry, record.and_then(validate_session))
+                    })
+                    .collect(),
+                Err(error) => return ImportReport::failed(error),
+            }
+        }
+        #[cfg(feature = "serde")]
+        ImportFormat::Json => match json_entries::<SessionRecord>(text) {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|(entry, record)| (entry, record.and_then(validate_session)))
+                .collect(),
+            Err(error) => return ImportReport::failed(error),
+        },
+        #[cfg(not(feature = "serde"))]
+        ImportFormat::Json => return ImportReport::failed(TimerError::UnsupportedFormat("JSON".to_string())),
+    };
+
+    let mut seen: HashSet<_> = existing.iter().map(|record| (record.started_at, record.ended_at)).collect();
+    ImportReport::collect(entries, |record| seen.insert((record.started_at, record.ended_at)))
+}
+
+fn preset_from_csv(fields: &[String]) -> Result<Preset, TimerError> {
+    Preset::new(fields[0].as_str(), parse_duration(&fields[1])?)
+}
+
+fn session_from_csv(fields: &[String]) -> Result<SessionRecord, TimerError> {
+    let time = |field: &str, value: &str| DateTime::parse_from_rfc3339(value).map_err(|_| invalid_field(field, value));
+    Ok(SessionRecord {
+        mode: TimerMode::from_key(&fields[3]).ok_or_else(|| invalid_field("mode", &fields[3]))?,
+        configured: seconds("configured_seconds", &fields[4])?,
+        elapsed: seconds("elapsed_seconds", &fields[5])?,
+        started_at: time("started_at", &fields[0])?,
+        ended_at: time("ended_at", &fields[1])?,
+        outcome: SessionOutcome::from_key(&fields[2]).ok_or_else(|| invalid_field("outcome", &fields[2]))?,
+        label: (!fields[6].is_empty()).then(|| fields[6].clone()),
+    })
+}
+
+/// Checks that a session ends after it starts and that a countdown's
+/// configured duration is one the timer accepts
+fn validate_session(record: SessionRecord) -> Result<SessionRecord, TimerError> {
+    if record.ended_at < record.started_at {
+        return Err(invalid_field("ended_at", &record.ended_at.to_rfc3339()));
+    }
+    if record.mode == TimerMode::Countdown {
+        validate_duration(record.configured)?;
+    }
+    Ok(record)
+}
+
+fn invalid_field(field: &str, value: &str) -> TimerError {
+    TimerError::InvalidImportField { field: field.to_string(), value: value.to_string() }
+}
+
+/// Reads decimal seconds such as "83.450" exactly, with up to nanosecond precision
+fn seconds(field: &str, value: &str) -> Result<Duration, TimerError> {
+    let invalid = || invalid_field(field, value);
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if whole.is_empty() || fraction.len() > 9 || !(whole.chars().chain(fraction.chars())).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let nanos = if fraction.is_empty() { 0 } else { format!("{:0<9}", fraction).parse().map_err(|_| invalid())? };
+    Ok(Duration::new(whole.parse().map_err(|_| invalid())?, nanos))
+}
+
+/// Splits CSV text into rows of fields, as RFC 4180 describes: fields may be
+/// quoted, with `""` for a quote, and quoted fields may span lines
+///
+/// Returns each row with the 1-based line it starts on. A quote left open at
+/// the end of the text makes the last row an error, keeping the rows before it.
+fn csv_rows(text: &str) -> Entries<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut in_quotes = false;
+    let mut characters = text.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '"' if in_quotes && characters.peek() == Some(&'"') => {
+                characters.next();
+                field.push('"');
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            '\n' if in_quotes => {
+                line += 1;
+                field.push('\n');
+            }
+            '\r' if !in_quotes => {}
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_line, Ok(std::mem::take(&mut row))));
+                line += 1;
+                row_line = line;
+            }
+            _ => field.push(character),
+        }
+    }
+    if in_quotes {
+        rows.push((row_line, Err(invalid_field("quoted field", &field))));
+    } else if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, Ok(row)));
+    }
+    rows.retain(|(_, fields)| !matches!(fields, Ok(fields) if fields.len() == 1 && fields[0].trim().is_empty()));
+    rows
+}
+
+/// Reads CSV rows and reorders their fields to match `columns`, by the
+/// names in the header row
+fn csv_entries(text: &str, columns: &[&str]) -> Result<Entries<Vec<String>>, TimerError> {
+    let mut rows = csv_rows(text).into_iter();
+    let Some((header_line, header)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let header = header.map_err(|error| TimerError::InvalidImportEntry { entry: header_line, source: Box::new(error) })?;
+    let positions = columns
+        .iter()
+        .map(|column| {
+            header.iter().position(|name| name.trim() == *column).ok_or_else(|| TimerError::InvalidImportEntry {
+                entry: header_line,
+                source: Box::new(invalid_field("header", &format!("missing column {}", column))),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows
+        .map(|(entry, fields)| {
+            let row = fields.and_then(|fields| {
+                if fields.len() == header.len() {
+                    Ok(positions.iter().map(|&position| fields[position].clone()).collect())
+                } else {
+                    Err(invalid_field("row", &format!("{} fields, expected {}", fields.len(), header.len())))
+                }
+            });
+            (entry, row)
+        })
+        .collect())
+}
+
+/// Reads a versioned JSON array, deserializing each entry on its own so one
+/// bad entry doesn't hide the others
+#[cfg(feature = "serde")]
+fn json_entries<T: serde::de::DeserializeOwned>(text: &str) -> Result<Entries<T>, TimerError> {
+    let values: Vec<serde_json::Value> = crate::timer::from_json(text)?;
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let entry = serde_json::from_value(value).map_err(|error| TimerError::InvalidJson(error.to_string()));
+            (index + 1, entry)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::export_csv;
+
+    #[test]
+    fn test_import_presets_from_csv() {
+        let csv = "name,duration\nTea,3m\n\"Standup, daily\",0:15:00\nNothing,0\nLong,100d\nSoup,\ntea,4m\n";
+        let existing = [Preset::new("Pasta", Duration::from_secs(600)).unwrap()];
+        let report = import_presets(csv, ImportFormat::Csv, &existing);
+
+        assert_eq!(
+            report.imported,
+            [
+                Preset::new("Tea", Duration::from_secs(180)).unwrap(),
+                Preset::new("Standup, daily", Duration::from_secs(900)).unwrap(),
+            ]
+        );
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(
+            report.errors,
+            [
+                TimerError::InvalidImportEntry { entry: 4, source: Box::new(TimerError::ZeroDuration) },
+                TimerError::InvalidImportEntry { entry: 5, source: Box::new(TimerError::DaysOutOfRange(100)) },
+                TimerError::InvalidImportEntry { entry: 6, source: Box::new(TimerError::EmptyDurationInput) },
+            ]
+        );
+        assert_eq!(report.errors[0].to_string(), "Entry 4: Timer duration cannot be zero");
+    }
+
+    #[test]
+    fn test_import_presets_skips_existing_names() {
+        let existing = [Preset::new("Tea", Duration::from_secs(180)).unwrap()];
+        let report = import_presets("duration,name\n5m,TEA\n", ImportFormat::Csv, &existing);
+        assert!(report.imported.is_empty());
+        assert_eq!(report.duplicates, 1);
+        assert!(report.errors.is_empty());
+    }
+
+    #[test]
+    fn test_csv_header_and_row_errors() {
+        let report = import_presets("name,minutes\nTea,3\n", ImportFormat::Csv, &[]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(
+            report.errors[0].to_string(),
+            "Entry 1: Invalid header \"missing column duration\""
+        );
+
+        let report = import_presets("name,duration\nTea\nPasta,\"10m\n", ImportFormat::Csv, &[]);
+        assert_eq!(report.errors.len(), 2);
+        assert!(matches!(report.errors[0], TimerError::InvalidImportEntry { entry: 2, .. }));
+        assert!(matches!(report.errors[1], TimerError::InvalidImportEntry { entry: 3, .. }));
+    }
+
+    #[test]
+    fn test_unterminated_quote_keeps_earlier_rows() {
+        let report = import_presets("name,duration\nTea,3m\n\"Pasta,10m\nSoup,5m\n", ImportFormat::Csv, &[]);
+        assert_eq!(report.imported, [Preset::new("Tea", Duration::from_secs(180)).unwrap()]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(
+            report.errors[0].to_string(),
+            "Entry 3: Invalid quoted field \"Pasta,10m\nSoup,5m\n\""
+        );
+    }
+
+    fn session(minute: i64, label: Option<&str>) -> SessionRecord {
+        let started_at = DateTime::parse_from_rfc3339("2024-01-01T09:00:00+01:00").unwrap()
+            + chrono::TimeDelta::minutes(minute);
+        SessionRecord {
+            mode: TimerMode::Countdown,
+            configured: Duration::from_secs(300),
+            elapsed: Duration::from_millis(299_500),
+            started_at,
+            ended_at: started_at + chrono::TimeDelta::minutes(5),
+            outcome: SessionOutcome::Completed,
+            label: label.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_import_sessions_round_trips_csv_export() {
+        let sessions = [session(0, Some("Notes, \"quoted\"\nacross lines")), session(10, None)];
+        let csv = export_csv(&sessions);
+        let report = import_sessions(&csv, ImportFormat::Csv, &[]);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.imported, sessions);
+
+        // Importing again against what is already there adds nothing
+        let report = import_sessions(&csv, ImportFormat::Csv, &sessions);
+        assert!(report.imported.is_empty());
+        assert_eq!(report.duplicates, 2);
+    }
+
+    #[test]
+    fn test_import_sessions_reports_each_bad_line() {
+        let mut csv = export_csv(&[session(0, None)]);
+        csv.push_str("2024-01-01T10:00:00+00:00,2024-01-01T10:05:00+00:00,paused,countdown,300.000,300.000,\r\n");
+        csv.push_str("2024-01-01T11:00:00+00:00,2024-01-01T11:05:00+00:00,completed,countdown,0.000,300.000,\r\n");
+        csv.push_str("2024-01-01T12:00:00+00:00,2024-01-01T11:05:00+00:00,completed,countdown,300.000,300.000,\r\n");
+        csv.push_str("2024-01-01T13:00:00+00:00,2024-01-01T13:05:00+00:00,completed,stopwatch,0.000,3.5.0,\r\n");
+        let report = import_sessions(&csv, ImportFormat::Csv, &[]);
+
+        assert_eq!(report.imported.len(), 1);
+        let messages: Vec<String> = report.errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "Entry 3: Invalid outcome \"paused\"",
+                "Entry 4: Timer duration cannot be zero",
+                "Entry 5: Invalid ended_at \"2024-01-01T11:05:00+00:00\"",
+                "Entry 6: Invalid elapsed_seconds \"3.5.0\"",
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_import_from_json() {
+        let sessions = vec![session(0, Some("Write report")), session(10, None)];
+        let json = crate::timer::to_json(&sessions).unwrap();
+        let report = import_sessions(&json, ImportFormat::Json, &sessions[..1]);
+        assert_eq!(report.imported, sessions[1..]);
+        assert_eq!(report.duplicates, 1);
+
+        let presets = r#"{"version": 1, "data": [
+            {"name": "Tea", "duration": {"secs": 180, "nanos": 0}},
+            {"name": "Broken"},
+            {"name": "Zero", "duration": {"secs": 0, "nanos": 0}}
+        ]}"#;
+        let report = import_presets(presets, ImportFormat::Json, &[]);
+        assert_eq!(report.imported, [Preset::new("Tea", Duration::from_secs(180)).unwrap()]);
+        assert!(matches!(&report.errors[0], TimerError::InvalidImportEntry { entry: 2, source } if matches!(**source, TimerError::InvalidJson(_))));
+        assert_eq!(
+            report.errors[1],
+            TimerError::InvalidImportEntry { entry: 3, source: Box::new(TimerError::ZeroDuration) }
+        );
+
+        let report = import_presets(r#"{"version": 7, "data": []}"#, ImportFormat::Json, &[]);
+        assert_eq!(report.errors, [TimerError::UnsupportedJsonVersion(7)]);
+    }
+
+    #[cfg(not(feature = "serde"))]
+    #[test]
+    fn test_json_needs_serde() {
+        let report = import_presets("[]", ImportFormat::Json, &[]);
+        assert_eq!(report.errors, [TimerError::UnsupportedFormat("JSON".to_string())]);
+        assert!(import_sessions("[]", ImportFormat::Json, &[]).imported.is_empty());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ImportFormat::from_path(Path::new("presets.CSV")), Ok(ImportFormat::Csv));
+        assert_eq!(ImportFormat::from_path(Path::new("history.json")), Ok(ImportFormat::Json));
+        assert_eq!(
+            ImportFormat::from_path(Path::new("history.ics")),
+            Err(TimerError::UnknownImportFormat("history.ics".to_string()))
+        );
+    }
+}
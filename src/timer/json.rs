@@ -0,0 +1,167 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::timer::TimerError;
+
+/// Version of the JSON representation written by [`to_json`]
+///
+/// Bump this whenever a serialized type changes shape, and keep reading the
+/// older versions where possible.
+///
+/// - 2: [`TimerState`](crate::timer::TimerState) gained `overtime_enabled`
+///   and `label`, which are off and unset when reading version 1
+pub const JSON_VERSION: u32 = 2;
+
+/// Oldest version [`from_json`] still reads
+const OLDEST_JSON_VERSION: u32 = 1;
+
+/// Envelope every JSON document is wrapped in, e.g.
+/// `{"version": 2, "data": {...}}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    /// Version of the representation of `data`
+    pub version: u32,
+    /// The serialized value
+    pub data: T,
+}
+
+/// Serializes a timer type such as [`TimerState`](crate::timer::TimerState)
+/// or [`PomodoroConfig`](crate::timer::PomodoroConfig) to pretty-printed,
+/// versioned JSON
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, TimerError> {
+    let document = Versioned { version: JSON_VERSION, data: value };
+    serde_json::to_string_pretty(&document).map_err(|error| TimerError::InvalidJson(error.to_string()))
+}
+
+/// Deserializes a value written by [`to_json`] in this or an older version,
+/// rejecting unknown versions
+///
+/// Every change so far only added fields with defaults, so older documents
+/// deserialize as they are.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, TimerError> {
+    let document: Versioned<serde_json::Value> =
+        serde_json::from_str(json).map_err(|error| TimerError::InvalidJson(error.to_string()))?;
+    if !(OLDEST_JSON_VERSION..=JSON_VERSION).contains(&document.version) {
+        return Err(TimerError::UnsupportedJsonVersion(document.version));
+    }
+    T::deserialize(document.data).map_err(|error| TimerError::InvalidJson(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::{
+        Clock, DisplayPrecision, MockClock, PomodoroConfig, PomodoroPhase, Segment, Sequence, TimerLogic,
+        TimerMode, TimerState,
+    };
+    use chrono::TimeDelta;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) {
+        let json = to_json(value).unwrap();
+        assert_eq!(&from_json::<T>(&json).unwrap(), value, "{json}");
+    }
+
+    #[test]
+    fn test_timer_state_schema() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        timer.set_target(clock.wall_time() + TimeDelta::minutes(90)).unwrap();
+        timer.start_timer();
+        clock.advance(Duration::from_millis(1500));
+        timer.lap();
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(timer.get_state()).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "version": 2,
+                "data": {
+                    "days": 0,
+                    "hours": 1,
+                    "minutes": 30,
+                    "seconds": 0,
+                    "mode": "countdown",
+                    "target": "2024-01-01T10:30:00Z",
+                    "remaining": { "secs": 5398, "nanos": 500_000_000 },
+                    "elapsed": { "secs": 1, "nanos": 500_000_000 },
+                    "overtime": { "secs": 0, "nanos": 0 },
+                    "laps": [{
+                        "number": 1,
+                        "elapsed": { "secs": 1, "nanos": 500_000_000 },
+                        "split": { "secs": 1, "nanos": 500_000_000 }
+                    }],
+                    "started_at": "2024-01-01T09:00:00Z",
+                    "is_running": true,
+                    "is_completed": false,
+                    "overtime_enabled": false,
+                    "label": null
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_timer_state_round_trips() {
+        round_trip(&TimerState::default());
+        round_trip(&TimerState::with_days(3, 4, 5, 6));
+        round_trip(&TimerState::stopwatch());
+
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 5, clock.clone()).unwrap();
+        timer.set_overtime(true);
+        timer.start_timer();
+        clock.advance(Duration::from_millis(7250));
+        timer.tick();
+        timer.lap();
+        round_trip(timer.get_state());
+    }
+
+    #[test]
+    fn test_reads_version_1_timer_state() {
+        let json = r#"{"version": 1, "data": {
+            "days": 0, "hours": 0, "minutes": 5, "seconds": 0, "mode": "countdown", "target": null,
+            "remaining": { "secs": 300, "nanos": 0 }, "elapsed": { "secs": 0, "nanos": 0 },
+            "overtime": { "secs": 0, "nanos": 0 }, "laps": [], "started_at": null,
+            "is_running": false, "is_completed": false
+        }}"#;
+        let state: TimerState = from_json(json).unwrap();
+        assert_eq!(state, TimerState::new(0, 5, 0));
+        assert!(!state.overtime_enabled);
+    }
+
+    #[test]
+    fn test_config_types_round_trip() {
+        round_trip(&PomodoroConfig::default());
+        round_trip(&PomodoroPhase::LongBreak);
+        round_trip(&TimerMode::Stopwatch);
+        round_trip(&DisplayPrecision::Hundredths);
+        round_trip(&Sequence::interval_training(
+            Duration::from_secs(60),
+            Duration::from_secs(20),
+            Duration::from_secs(10),
+            3,
+            Duration::from_secs(30),
+        ));
+        round_trip(&Segment::new("Plank", Duration::from_secs(45), 0x17a2b8));
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&PomodoroPhase::ShortBreak).unwrap()).unwrap();
+        assert_eq!(json, json!({ "version": 2, "data": "short_break" }));
+    }
+
+    #[test]
+    fn test_rejects_other_versions_and_bad_json() {
+        assert_eq!(
+            from_json::<TimerMode>(r#"{"version": 3, "data": "countdown"}"#),
+            Err(TimerError::UnsupportedJsonVersion(3))
+        );
+        assert_eq!(
+            from_json::<TimerMode>(r#"{"version": 0, "data": "countdown"}"#),
+            Err(TimerError::UnsupportedJsonVersion(0))
+        );
+        assert!(matches!(from_json::<TimerMode>(r#"{"data": "countdown"}"#), Err(TimerError::InvalidJson(_))));
+        assert!(matches!(from_json::<TimerMode>(r#"{"version": 1, "data": "sideways"}"#), Err(TimerError::InvalidJson(_))));
+        assert!(matches!(from_json::<TimerState>("not json"), Err(TimerError::InvalidJson(_))));
+    }
+}
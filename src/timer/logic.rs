@@ -1,3 +1,14 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveTime, TimeDelta, TimeZone};
+
+use crate::timer::{
+    Clock, DisplayPrecision, Lap, SessionOutcome, SessionRecord, SystemClock, TimerError, TimerMode, TimerState,
+};
+
+/// Longest countdown that can be configured, in days
+pub const MAX_DAYS: u32 = 99;
+
 /// Validates time input values according to standard time constraints
 /// 
 /// # Arguments
@@ -7,54 +18,133 @@
 /// 
 /// # Returns
 /// * `Ok(())` if all values are valid
-/// * `Err(String)` with descriptive error message if any value is invalid
-pub fn validate_time(hours: u32, minutes: u32, seconds: u32) -> Result<(), String> {
+/// * `Err(TimerError)` carrying the offending value if any value is invalid
+pub fn validate_time(hours: u32, minutes: u32, seconds: u32) -> Result<(), TimerError> {
+    validate_time_with_days(0, hours, minutes, seconds)
+}
+
+/// Validates time input values like [`validate_time`], with a leading number
+/// of days (0-[`MAX_DAYS`]) for countdowns longer than a day
+pub fn validate_time_with_days(days: u32, hours: u32, minutes: u32, seconds: u32) -> Result<(), TimerError> {
+    if days > MAX_DAYS {
+        return Err(TimerError::DaysOutOfRange(days));
+    }
+    
     if hours > 23 {
-        return Err(format!("Hours must be between 0 and 23, got {}", hours));
+        return Err(TimerError::HoursOutOfRange(hours));
     }
     
     if minutes > 59 {
-        return Err(format!("Minutes must be between 0 and 59, got {}", minutes));
+        return Err(TimerError::MinutesOutOfRange(minutes));
     }
     
     if seconds > 59 {
-        return Err(format!("Seconds must be between 0 and 59, got {}", seconds));
+        return Err(TimerError::SecondsOutOfRange(seconds));
     }
     
     // Check if all values are zero (invalid timer)
-    if hours == 0 && minutes == 0 && seconds == 0 {
-        return Err("Timer duration cannot be zero".to_string());
+    if days == 0 && hours == 0 && minutes == 0 && seconds == 0 {
+        return Err(TimerError::ZeroDuration);
     }
     
     Ok(())
 }
 
-use crate::timer::TimerState;
+/// Validates a [`Duration`] as a timer duration, ignoring any fraction of a second
+pub fn validate_duration(duration: Duration) -> Result<(), TimerError> {
+    let (days, hours, minutes, seconds) = split_duration(duration);
+    validate_time_with_days(days, hours, minutes, seconds)
+}
+
+/// Splits the whole seconds of `duration` into days, hours, minutes and seconds
+fn split_duration(duration: Duration) -> (u32, u32, u32, u32) {
+    let total_seconds = duration.as_secs();
+    let days = u32::try_from(total_seconds / 86_400).unwrap_or(u32::MAX);
+    let hours = ((total_seconds % 86_400) / 3600) as u32;
+    let minutes = ((total_seconds % 3600) / 60) as u32;
+    let seconds = (total_seconds % 60) as u32;
+    (days, hours, minutes, seconds)
+}
 
 /// TimerLogic manages the timer state and provides methods for controlling the timer
+///
+/// The countdown is derived from elapsed monotonic time rather than from the
+/// number of `tick()` calls, so a late or skipped tick never makes it drift.
 pub struct TimerLogic {
     state: TimerState,
     callback: Option<Box<dyn Fn(TimerState) + Send>>,
+    /// Called with a record of every run that ends
+    session_callback: Option<Box<dyn Fn(SessionRecord) + Send>>,
+    /// Source of monotonic time
+    clock: Box<dyn Clock>,
+    /// When the current run was first started
+    started_at: Option<Instant>,
+    /// When the timer was paused, if it is currently paused
+    paused_at: Option<Instant>,
+    /// Wall-clock time the current run was first started, until it is recorded
+    run_started_wall: Option<DateTime<FixedOffset>>,
+    /// Total time spent paused since `started_at`
+    paused_duration: Duration,
+    /// Running time carried over from before `started_at` by a restored run
+    carried: Duration,
+    /// Resolution used for formatting and change notifications
+    precision: DisplayPrecision,
 }
 
 impl TimerLogic {
     /// Creates a new TimerLogic instance with default state
     pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    /// Creates a new TimerLogic instance with default state that reads time from `clock`
+    pub fn with_clock<C: Clock + 'static>(clock: C) -> Self {
         Self {
             state: TimerState::default(),
             callback: None,
+            session_callback: None,
+            clock: Box::new(clock),
+            started_at: None,
+            paused_at: None,
+            run_started_wall: None,
+            paused_duration: Duration::ZERO,
+            carried: Duration::ZERO,
+            precision: DisplayPrecision::default(),
         }
     }
 
     /// Creates a new TimerLogic instance with specified time
-    pub fn with_time(hours: u32, minutes: u32, seconds: u32) -> Result<Self, String> {
+    pub fn with_time(hours: u32, minutes: u32, seconds: u32) -> Result<Self, TimerError> {
+        Self::with_time_and_clock(hours, minutes, seconds, SystemClock)
+    }
+
+    /// Creates a new TimerLogic instance with specified time that reads time from `clock`
+    pub fn with_time_and_clock<C: Clock + 'static>(
+        hours: u32,
+        minutes: u32,
+        seconds: u32,
+        clock: C,
+    ) -> Result<Self, TimerError> {
         validate_time(hours, minutes, seconds)?;
         Ok(Self {
             state: TimerState::new(hours, minutes, seconds),
-            callback: None,
+            ..Self::with_clock(clock)
         })
     }
 
+    /// Creates a new TimerLogic instance in stopwatch mode
+    pub fn stopwatch() -> Self {
+        Self::stopwatch_with_clock(SystemClock)
+    }
+
+    /// Creates a new TimerLogic instance in stopwatch mode that reads time from `clock`
+    pub fn stopwatch_with_clock<C: Clock + 'static>(clock: C) -> Self {
+        Self {
+            state: TimerState::stopwatch(),
+            ..Self::with_clock(clock)
+        }
+    }
+
     /// Sets a callback function to be called when the timer state changes
     pub fn set_state_callback<F>(&mut self, callback: F)
     where
@@ -63,42 +153,295 @@ impl TimerLogic {
         self.callback = Some(Box::new(callback));
     }
 
+    /// Sets a callback function to be called with a [`SessionRecord`] when a run ends
+    ///
+    /// A run ends when a countdown completes, or when a started run is reset
+    /// or replaced by new settings. A countdown in overtime mode is recorded
+    /// as completed once it stops, e.g. on reset.
+    pub fn set_session_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(SessionRecord) + Send + 'static,
+    {
+        self.session_callback = Some(Box::new(callback));
+    }
+
+    /// Gets the label copied into session records
+    pub fn label(&self) -> Option<&str> {
+        self.state.label.as_deref()
+    }
+
+    /// Sets what the timer is being used for, e.g. "Write report"
+    ///
+    /// The label applies to the run that is going when it ends, so it can be
+    /// changed after starting. An empty label is treated as no label.
+    pub fn set_label(&mut self, label: Option<String>) {
+        let label = label.filter(|label| !label.trim().is_empty());
+        if label != self.state.label {
+            self.state.label = label;
+            self.notify_state_change();
+        }
+    }
+
     /// Gets the current timer state
     pub fn get_state(&self) -> &TimerState {
         &self.state
     }
 
-    /// Sets new time values for the timer
-    pub fn set_time(&mut self, hours: u32, minutes: u32, seconds: u32) -> Result<(), String> {
-        validate_time(hours, minutes, seconds)?;
-        self.state = TimerState::new(hours, minutes, seconds);
+    /// Gets whether the timer counts down or up
+    pub fn mode(&self) -> TimerMode {
+        self.state.mode
+    }
+
+    /// Switches between countdown and stopwatch mode
+    ///
+    /// Switching discards the current run. A countdown starts out with no
+    /// duration, so `set_time` must be called before it can be started.
+    pub fn set_mode(&mut self, mode: TimerMode) {
+        if mode == self.state.mode {
+            return;
+        }
+        self.end_session(SessionOutcome::Abandoned);
+        let overtime_enabled = self.state.overtime_enabled;
+        let label = self.state.label.take();
+        self.state = match mode {
+            TimerMode::Countdown => TimerState::default(),
+            TimerMode::Stopwatch => TimerState::stopwatch(),
+        };
+        self.state.overtime_enabled = overtime_enabled;
+        self.state.label = label;
+        self.clear_run();
+        self.notify_state_change();
+    }
+
+    /// Gets the precision used to format the remaining time
+    pub fn display_precision(&self) -> DisplayPrecision {
+        self.precision
+    }
+
+    /// Sets the precision used to format the remaining time
+    ///
+    /// Finer precisions make `tick()` notify the state callback once per
+    /// displayed unit instead of once per second.
+    pub fn set_display_precision(&mut self, precision: DisplayPrecision) {
+        self.precision = precision;
+    }
+
+    /// Checks if overtime mode is enabled
+    pub fn overtime_enabled(&self) -> bool {
+        self.state.overtime_enabled
+    }
+
+    /// Enables or disables overtime mode
+    ///
+    /// In overtime mode a countdown still completes when it reaches zero, but
+    /// keeps running and counts the time past zero in `TimerState::overtime`
+    /// until it is reset. Disabling it while in overtime stops the timer.
+    pub fn set_overtime(&mut self, enabled: bool) {
+        if enabled == self.state.overtime_enabled {
+            return;
+        }
+        self.state.overtime_enabled = enabled;
+        if !enabled && self.state.is_completed && (self.state.is_running || self.paused_at.is_some()) {
+            self.refresh(self.clock.now());
+            self.end_session(SessionOutcome::Completed);
+            self.state.is_running = false;
+            self.state.started_at = None;
+            self.paused_at = None;
+        }
+        self.notify_state_change();
+    }
+
+    /// Sets new time values for the timer, switching it to countdown mode
+    pub fn set_time(&mut self, hours: u32, minutes: u32, seconds: u32) -> Result<(), TimerError> {
+        self.set_time_with_days(0, hours, minutes, seconds)
+    }
+
+    /// Sets new time values including whole days, switching to countdown mode
+    pub fn set_time_with_days(
+        &mut self,
+        days: u32,
+        hours: u32,
+        minutes: u32,
+        seconds: u32,
+    ) -> Result<(), TimerError> {
+        validate_time_with_days(days, hours, minutes, seconds)?;
+        self.end_session(SessionOutcome::Abandoned);
+        self.state = TimerState {
+            overtime_enabled: self.state.overtime_enabled,
+            label: self.state.label.take(),
+            ..TimerState::with_days(days, hours, minutes, seconds)
+        };
+        self.clear_run();
         self.notify_state_change();
         Ok(())
     }
 
-    /// Starts the timer
+    /// Sets the countdown duration from a [`Duration`], switching to countdown mode
+    ///
+    /// Any fraction of a second is dropped before validation.
+    pub fn set_duration(&mut self, duration: Duration) -> Result<(), TimerError> {
+        let (days, hours, minutes, seconds) = split_duration(duration);
+        self.set_time_with_days(days, hours, minutes, seconds)
+    }
+
+    /// Sets a countdown to the next time the local wall clock shows `time`,
+    /// switching to countdown mode
+    ///
+    /// A time that has already passed today, or is exactly now, counts down to
+    /// that time tomorrow, so "until 01:00" set late in the evening crosses
+    /// midnight. The target is resolved in the clock's time zone on its own
+    /// date, so a daylight saving change in between is accounted for. A time
+    /// repeated when the clocks go back means its first occurrence, and a time
+    /// skipped when they go forward means the instant it would have been had
+    /// the clocks not changed.
+    pub fn set_target_time(&mut self, time: NaiveTime) -> Result<(), TimerError> {
+        let now = self.clock.wall_time();
+        let mut target = now.date_naive().and_time(time);
+        if target <= now.naive_local() {
+            target += TimeDelta::days(1);
+        }
+        let resolved = match self.clock.local_datetime(target) {
+            // Read the skipped time in the offset from before the gap
+            LocalResult::None => self
+                .clock
+                .local_datetime(target - TimeDelta::days(1))
+                .earliest()
+                .and_then(|before| before.offset().from_local_datetime(&target).single()),
+            resolved => resolved.earliest(),
+        };
+        let target = resolved.ok_or_else(|| TimerError::InvalidTimeOfDay(time.format("%H:%M").to_string()))?;
+        self.set_target(target)
+    }
+
+    /// Sets a countdown to an absolute wall-clock time, switching to countdown mode
+    ///
+    /// The configured duration is the time left when the target is set. While
+    /// running, the remaining time is measured against the wall clock rather
+    /// than the monotonic clock, so it stays correct if the system clock is
+    /// changed.
+    pub fn set_target(&mut self, target: DateTime<FixedOffset>) -> Result<(), TimerError> {
+        let remaining = (target - self.clock.wall_time()).to_std().unwrap_or(Duration::ZERO);
+        if remaining.is_zero() {
+            return Err(TimerError::TargetInPast(target));
+        }
+
+        let whole_seconds = Duration::from_secs(remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0));
+        let (days, hours, minutes, seconds) = split_duration(whole_seconds);
+        validate_time_with_days(days, hours, minutes, seconds)?;
+        self.end_session(SessionOutcome::Abandoned);
+        self.state = TimerState {
+            overtime_enabled: self.state.overtime_enabled,
+            label: self.state.label.take(),
+            ..TimerState::with_days(days, hours, minutes, seconds)
+        };
+        self.state.target = Some(target);
+        self.state.remaining = remaining;
+        self.clear_run();
+        self.notify_state_change();
+        Ok(())
+    }
+
+    /// Starts the timer, or resumes it if it was paused
+    ///
+    /// A completed countdown can only be resumed if it was paused in overtime.
     pub fn start_timer(&mut self) {
-        if !self.state.is_completed && self.state.remaining_seconds > 0 {
+        if self.state.target.is_some() && !self.state.is_running {
+            // The target may have come closer, or passed, while stopped
+            self.refresh(self.clock.now());
+        }
+        let can_run = match self.state.mode {
+            TimerMode::Countdown if self.state.is_completed => self.state.overtime_enabled && self.is_paused(),
+            TimerMode::Countdown => !self.state.remaining.is_zero(),
+            TimerMode::Stopwatch => true,
+        };
+        if !self.state.is_running && can_run {
+            let now = self.clock.now();
+            match self.paused_at.take() {
+                Some(paused_at) => self.paused_duration += now.saturating_duration_since(paused_at),
+                None => {
+                    self.started_at = Some(now);
+                    let carried = TimeDelta::from_std(self.carried).unwrap_or(TimeDelta::MAX);
+                    self.run_started_wall = self.clock.wall_time().checked_sub_signed(carried);
+                }
+            }
+            self.refresh(now);
+            let elapsed = TimeDelta::from_std(self.elapsed_at(now)).unwrap_or(TimeDelta::MAX);
+            self.state.started_at = self.clock.wall_time().checked_sub_signed(elapsed);
             self.state.is_running = true;
-            self.state.is_completed = false;
             self.notify_state_change();
         }
     }
 
+    /// Starts the timer as if it had already been running for `elapsed`
+    ///
+    /// Used to carry the time a countdown ran past zero into the one that
+    /// follows it, so a chain of countdowns keeps pace with the clock. Does
+    /// nothing if the timer is running or paused; call [`tick`](Self::tick)
+    /// afterwards to complete a countdown shorter than `elapsed`.
+    pub fn start_timer_after(&mut self, elapsed: Duration) {
+        if self.state.is_running || self.is_paused() {
+            return;
+        }
+        self.carried = elapsed;
+        self.start_timer();
+        if !self.state.is_running {
+            self.carried = Duration::ZERO;
+        }
+    }
+
     /// Pauses the timer
     pub fn pause_timer(&mut self) {
         if self.state.is_running {
+            let now = self.clock.now();
+            self.paused_at = Some(now);
+            self.refresh(now);
+            self.state.started_at = None;
             self.state.is_running = false;
             self.notify_state_change();
         }
     }
 
     /// Resets the timer to its original time
+    ///
+    /// A countdown to an absolute time keeps its target, so it shows the time
+    /// now left until the target.
     pub fn reset_timer(&mut self) {
+        self.end_session(SessionOutcome::Reset);
         self.state.reset();
+        self.clear_run();
+        if let Some(target) = self.state.target {
+            self.state.remaining = (target - self.clock.wall_time()).to_std().unwrap_or(Duration::ZERO);
+        }
         self.notify_state_change();
     }
 
+    /// Records a lap at the current elapsed time
+    ///
+    /// The lap is appended to the state's lap list and the state callback is
+    /// notified. Returns the recorded lap, or None if the timer isn't running.
+    pub fn lap(&mut self) -> Option<Lap> {
+        if !self.state.is_running {
+            return None;
+        }
+
+        self.refresh(self.clock.now());
+        let elapsed = self.state.elapsed;
+        let previous = self.state.laps.last().map_or(Duration::ZERO, |lap| lap.elapsed);
+        let lap = Lap {
+            number: self.state.laps.len() + 1,
+            elapsed,
+            split: elapsed.saturating_sub(previous),
+        };
+        self.state.laps.push(lap);
+        self.notify_state_change();
+        Some(lap)
+    }
+
+    /// Checks if the timer was started and is currently paused
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
     /// Checks if the timer is currently running
     pub fn is_running(&self) -> bool {
         self.state.is_running
@@ -109,34 +452,191 @@ impl TimerLogic {
         self.state.is_completed
     }
 
-    /// Gets the remaining time as a formatted string
+    /// Gets the remaining time as a string formatted at the display precision
     pub fn get_remaining_time_string(&self) -> String {
-        self.state.format_remaining_time()
+        self.state.format_remaining_time_with(self.precision)
     }
 
-    /// Called every second to update countdown when timer is running
-    /// Returns true if timer completed, false otherwise
+    /// Gets the time to show for the current mode, formatted at the display precision
+    ///
+    /// This is the remaining time of a countdown or the elapsed time of a stopwatch.
+    pub fn get_display_time_string(&self) -> String {
+        self.state.format_display_time_with(self.precision)
+    }
+
+    /// Gets how far a completed countdown has run past zero
+    ///
+    /// Unlike the overtime in the state, this is measured whether or not
+    /// overtime is enabled. Zero unless a countdown has completed.
+    pub fn overrun(&self) -> Duration {
+        if self.state.mode != TimerMode::Countdown || !self.state.is_completed {
+            return Duration::ZERO;
+        }
+        match self.state.target {
+            Some(target) => (self.clock.wall_time() - target).to_std().unwrap_or(Duration::ZERO),
+            None => self.elapsed().saturating_sub(self.state.duration()),
+        }
+    }
+
+    /// Gets the time elapsed on the current run, excluding time spent paused
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(self.clock.now())
+    }
+
+    /// Restores a state saved by an earlier session, e.g. from
+    /// [`StateStore`](crate::timer::StateStore)
+    ///
+    /// A run that was going when the state was saved keeps going, including
+    /// the time that passed in between as measured by the wall clock; a
+    /// paused run stays paused, and a completed run stays completed without
+    /// being recorded again. The saved label replaces the current one. Call
+    /// [`tick`](Self::tick) afterwards to complete a countdown that ran out in
+    /// the meantime.
+    pub fn restore_state(&mut self, state: TimerState) {
+        let now = self.clock.now();
+        let elapsed = match state.started_at {
+            Some(started_at) if state.is_running => {
+                (self.clock.wall_time() - started_at).to_std().unwrap_or(Duration::ZERO)
+            }
+            _ => state.elapsed,
+        };
+        // A completed run was recorded when it completed, so only a run that
+        // is still going, or paused in overtime, is rebuilt
+        let is_paused = !state.is_running && !elapsed.is_zero() && (!state.is_completed || state.is_overtime());
+
+        self.end_session(SessionOutcome::Abandoned);
+        self.state = state;
+        self.clear_run();
+        if self.state.is_running || is_paused {
+            let elapsed_wall = TimeDelta::from_std(elapsed).unwrap_or(TimeDelta::MAX);
+            self.run_started_wall = self.clock.wall_time().checked_sub_signed(elapsed_wall);
+            self.started_at = Some(now);
+            self.paused_at = is_paused.then_some(now);
+            self.carried = elapsed;
+            self.refresh(now);
+        }
+        self.notify_state_change();
+    }
+
+    /// Refreshes the elapsed and remaining time from the monotonic clock
+    ///
+    /// Can be called at any rate; the state callback only fires when the
+    /// displayed value changes at the current display precision. Returns
+    /// true if the timer completed on this call, false otherwise. A
+    /// stopwatch never completes, and a countdown in overtime mode completes
+    /// once but keeps running.
     pub fn tick(&mut self) -> bool {
-        if !self.state.is_running || self.state.is_completed {
+        if !self.state.is_running {
             return false;
         }
 
-        if self.state.remaining_seconds > 0 {
-            self.state.remaining_seconds -= 1;
+        let displayed = self.displayed_units();
+        self.refresh(self.clock.now());
+        if self.displayed_units() != displayed {
+            self.notify_state_change();
+        }
+
+        // Check if timer completed
+        if self.state.mode == TimerMode::Countdown
+            && self.state.remaining.is_zero()
+            && !self.state.is_completed
+        {
+            self.state.is_running = self.state.overtime_enabled;
+            if !self.state.overtime_enabled {
+                self.state.started_at = None;
+            }
+            self.state.is_completed = true;
             self.notify_state_change();
-            
-            // Check if timer completed
-            if self.state.remaining_seconds == 0 {
-                self.state.is_running = false;
-                self.state.is_completed = true;
-                self.notify_state_change();
-                return true;
+            if !self.state.overtime_enabled {
+                self.end_session(SessionOutcome::Completed);
             }
+            return true;
         }
-        
+
         false
     }
 
+    /// Running time between `started_at` and `now`, minus any paused time
+    fn elapsed_at(&self, now: Instant) -> Duration {
+        match self.started_at {
+            Some(started_at) => self
+                .paused_at
+                .unwrap_or(now)
+                .saturating_duration_since(started_at)
+                .saturating_sub(self.paused_duration)
+                + self.carried,
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Updates the elapsed and remaining time in the state to match `now`
+    fn refresh(&mut self, now: Instant) {
+        self.state.elapsed = self.elapsed_at(now);
+        match self.state.target {
+            Some(target) => {
+                let until_target = target - self.clock.wall_time();
+                self.state.remaining = until_target.to_std().unwrap_or(Duration::ZERO);
+                if self.state.overtime_enabled {
+                    self.state.overtime = (-until_target).to_std().unwrap_or(Duration::ZERO);
+                }
+            }
+            None => {
+                self.state.remaining = self.state.duration().saturating_sub(self.state.elapsed);
+                if self.state.overtime_enabled && self.state.mode == TimerMode::Countdown {
+                    self.state.overtime = self.state.elapsed.saturating_sub(self.state.duration());
+                }
+            }
+        }
+    }
+
+    /// Value currently shown for this mode, in units of the display precision
+    ///
+    /// Overtime counts as negative so crossing zero is seen as a change.
+    fn displayed_units(&self) -> i128 {
+        match self.state.mode {
+            TimerMode::Countdown => {
+                self.precision.units(self.state.remaining) as i128
+                    - self.precision.elapsed_units(self.state.overtime) as i128
+            }
+            TimerMode::Stopwatch => self.precision.elapsed_units(self.state.elapsed) as i128,
+        }
+    }
+
+    /// Reports the current run to the session callback, if it was started and
+    /// hasn't been reported yet
+    ///
+    /// A run that has completed is always reported as completed, whatever
+    /// ended it.
+    fn end_session(&mut self, outcome: SessionOutcome) {
+        let Some(started_at) = self.run_started_wall.take() else {
+            return;
+        };
+        let record = SessionRecord {
+            mode: self.state.mode,
+            configured: match self.state.mode {
+                TimerMode::Countdown => self.state.duration(),
+                TimerMode::Stopwatch => Duration::ZERO,
+            },
+            elapsed: self.elapsed_at(self.clock.now()),
+            started_at,
+            ended_at: self.clock.wall_time(),
+            outcome: if self.state.is_completed { SessionOutcome::Completed } else { outcome },
+            label: self.state.label.clone(),
+        };
+        if let Some(ref callback) = self.session_callback {
+            callback(record);
+        }
+    }
+
+    /// Forgets the start and pause timestamps of the current run
+    fn clear_run(&mut self) {
+        self.started_at = None;
+        self.paused_at = None;
+        self.run_started_wall = None;
+        self.paused_duration = Duration::ZERO;
+        self.carried = Duration::ZERO;
+    }
+
     /// Private method to notify state changes via callback
     fn notify_state_change(&self) {
         if let Some(ref callback) = self.callback {
@@ -153,7 +653,10 @@ impl Default for TimerLogic {
 
 #[cfg(test)]
 mod tests {
+    use chrono::NaiveDateTime;
+
     use super::*;
+    use crate::timer::MockClock;
 
     #[test]
     fn test_valid_time_inputs() {
@@ -171,7 +674,8 @@ mod tests {
         assert!(validate_time(25, 30, 45).is_err());
         
         let error = validate_time(24, 0, 0).unwrap_err();
-        assert!(error.contains("Hours must be between 0 and 23"));
+        assert_eq!(error, TimerError::HoursOutOfRange(24));
+        assert!(error.to_string().contains("Hours must be between 0 and 23"));
     }
 
     #[test]
@@ -180,7 +684,8 @@ mod tests {
         assert!(validate_time(12, 75, 30).is_err());
         
         let error = validate_time(0, 60, 0).unwrap_err();
-        assert!(error.contains("Minutes must be between 0 and 59"));
+        assert_eq!(error, TimerError::MinutesOutOfRange(60));
+        assert!(error.to_string().contains("Minutes must be between 0 and 59"));
     }
 
     #[test]
@@ -189,7 +694,8 @@ mod tests {
         assert!(validate_time(5, 30, 90).is_err());
         
         let error = validate_time(0, 0, 60).unwrap_err();
-        assert!(error.contains("Seconds must be between 0 and 59"));
+        assert_eq!(error, TimerError::SecondsOutOfRange(60));
+        assert!(error.to_string().contains("Seconds must be between 0 and 59"));
     }
 
     #[test]
@@ -197,10 +703,38 @@ mod tests {
         assert!(validate_time(0, 0, 0).is_err());
         
         let error = validate_time(0, 0, 0).unwrap_err();
-        assert!(error.contains("Timer duration cannot be zero"));
+        assert_eq!(error, TimerError::ZeroDuration);
+        assert!(error.to_string().contains("Timer duration cannot be zero"));
+    }
+
+    #[test]
+    fn test_days_extend_the_range() {
+        assert!(validate_time_with_days(3, 0, 0, 0).is_ok());
+        assert!(validate_time_with_days(MAX_DAYS, 23, 59, 59).is_ok());
+        assert_eq!(validate_time_with_days(MAX_DAYS + 1, 0, 0, 0), Err(TimerError::DaysOutOfRange(100)));
+        assert_eq!(validate_time_with_days(1, 24, 0, 0), Err(TimerError::HoursOutOfRange(24)));
+        assert_eq!(validate_time_with_days(0, 0, 0, 0), Err(TimerError::ZeroDuration));
     }
 
     // TimerLogic tests
+    #[test]
+    fn test_day_scale_countdown() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        timer.set_duration(Duration::from_secs(36 * 3600)).unwrap();
+        assert_eq!(timer.get_state().days, 1);
+        assert_eq!(timer.get_state().hours, 12);
+        assert_eq!(timer.get_remaining_time_string(), "1:12:00:00");
+
+        timer.start_timer();
+        clock.advance(Duration::from_secs(12 * 3600 + 1));
+        timer.tick();
+        assert_eq!(timer.get_remaining_time_string(), "23:59:59");
+
+        timer.set_time_with_days(3, 0, 0, 0).unwrap();
+        assert_eq!(timer.get_state().duration(), Duration::from_secs(3 * 86_400));
+    }
+
     #[test]
     fn test_timer_logic_creation() {
         let timer = TimerLogic::new();
@@ -266,7 +800,8 @@ mod tests {
 
     #[test]
     fn test_tick_functionality() {
-        let mut timer = TimerLogic::with_time(0, 0, 3).unwrap();
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 3, clock.clone()).unwrap();
         
         // Tick should return false when timer is not running
         assert!(!timer.tick());
@@ -277,19 +812,25 @@ mod tests {
         assert!(timer.is_running());
         assert!(!timer.is_completed());
         
-        // First tick: 3 -> 2 seconds
+        // Ticking without time passing doesn't change the countdown
+        assert!(!timer.tick());
+        assert!(!timer.tick());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:03");
+        
+        // One second elapsed: 3 -> 2 seconds
+        clock.advance(Duration::from_secs(1));
         assert!(!timer.tick());
         assert_eq!(timer.get_remaining_time_string(), "00:00:02");
         assert!(timer.is_running());
         assert!(!timer.is_completed());
         
-        // Second tick: 2 -> 1 seconds
+        // Part of a second only rounds the display up
+        clock.advance(Duration::from_millis(500));
         assert!(!timer.tick());
-        assert_eq!(timer.get_remaining_time_string(), "00:00:01");
-        assert!(timer.is_running());
-        assert!(!timer.is_completed());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:02");
         
-        // Final tick: 1 -> 0 seconds (completion)
+        // Final stretch: 1.5 -> 0 seconds (completion)
+        clock.advance(Duration::from_millis(1500));
         assert!(timer.tick());
         assert_eq!(timer.get_remaining_time_string(), "00:00:00");
         assert!(!timer.is_running());
@@ -300,12 +841,31 @@ mod tests {
         assert_eq!(timer.get_remaining_time_string(), "00:00:00");
     }
 
+    #[test]
+    fn test_missed_ticks_do_not_drift() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(2, 0, 0, clock.clone()).unwrap();
+        timer.start_timer();
+        
+        // A long stall between ticks is caught up in a single tick
+        clock.advance(Duration::from_secs(3600 + 59 * 60 + 30));
+        assert!(!timer.tick());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:30");
+        assert_eq!(timer.elapsed(), Duration::from_secs(7170));
+        
+        clock.advance(Duration::from_secs(30));
+        assert!(timer.tick());
+        assert!(timer.is_completed());
+    }
+
     #[test]
     fn test_tick_when_paused() {
-        let mut timer = TimerLogic::with_time(0, 0, 5).unwrap();
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 5, clock.clone()).unwrap();
         timer.start_timer();
         
-        // Tick once to decrement
+        // One second elapses while running
+        clock.advance(Duration::from_secs(1));
         assert!(!timer.tick());
         assert_eq!(timer.get_remaining_time_string(), "00:00:04");
         
@@ -313,13 +873,18 @@ mod tests {
         timer.pause_timer();
         assert!(!timer.is_running());
         
-        // Tick should not decrement when paused
+        // Time spent paused should not count down
+        clock.advance(Duration::from_secs(10));
         assert!(!timer.tick());
         assert_eq!(timer.get_remaining_time_string(), "00:00:04");
+        assert_eq!(timer.elapsed(), Duration::from_secs(1));
         
-        // Resume and tick should work again
+        // Resume and the countdown continues from where it was paused
         timer.start_timer();
         assert!(!timer.tick());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:04");
+        clock.advance(Duration::from_secs(1));
+        assert!(!timer.tick());
         assert_eq!(timer.get_remaining_time_string(), "00:00:03");
     }
 
@@ -327,7 +892,8 @@ mod tests {
     fn test_callback_on_tick() {
         use std::sync::{Arc, Mutex};
         
-        let mut timer = TimerLogic::with_time(0, 0, 2).unwrap();
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 2, clock.clone()).unwrap();
         let callback_states = Arc::new(Mutex::new(Vec::new()));
         let callback_states_clone = callback_states.clone();
         
@@ -336,15 +902,392 @@ mod tests {
         });
         
         timer.start_timer(); // Should trigger callback
+        timer.tick(); // No time passed, no callback
+        clock.advance(Duration::from_secs(1));
         timer.tick(); // Should trigger callback
+        clock.advance(Duration::from_secs(1));
         timer.tick(); // Should trigger callback twice (tick + completion)
         
         let states = callback_states.lock().unwrap();
         assert_eq!(states.len(), 4); // start + tick + tick + completion
         
         // Check that remaining seconds decreased properly
-        assert_eq!(states[1].remaining_seconds, 1);
-        assert_eq!(states[2].remaining_seconds, 0);
+        assert_eq!(states[1].remaining_seconds(), 1);
+        assert_eq!(states[2].remaining_seconds(), 0);
         assert!(states[3].is_completed);
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_reset_discards_elapsed_time() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 1, 0, clock.clone()).unwrap();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(20));
+        timer.pause_timer();
+        clock.advance(Duration::from_secs(5));
+        
+        timer.reset_timer();
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+        assert_eq!(timer.get_remaining_time_string(), "00:01:00");
+        
+        // A fresh start counts the full duration again
+        timer.start_timer();
+        clock.advance(Duration::from_secs(59));
+        assert!(!timer.tick());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:01");
+        clock.advance(Duration::from_secs(1));
+        assert!(timer.tick());
+    }
+
+    #[test]
+    fn test_hundredths_precision() {
+        use std::sync::{Arc, Mutex};
+        
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 2, clock.clone()).unwrap();
+        timer.set_display_precision(DisplayPrecision::Hundredths);
+        assert_eq!(timer.get_remaining_time_string(), "00:00:02.00");
+        
+        let notifications = Arc::new(Mutex::new(0));
+        let notifications_clone = notifications.clone();
+        timer.set_state_callback(move |_| *notifications_clone.lock().unwrap() += 1);
+        
+        timer.start_timer();
+        clock.advance(Duration::from_millis(755));
+        assert!(!timer.tick());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:01.25");
+        assert_eq!(timer.get_state().remaining, Duration::from_millis(1245));
+        
+        // Less than a hundredth later the display is unchanged, so no notification
+        clock.advance(Duration::from_millis(4));
+        assert!(!timer.tick());
+        assert_eq!(*notifications.lock().unwrap(), 2); // start + tick
+        
+        clock.advance(Duration::from_millis(1241));
+        assert!(timer.tick());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:00.00");
+    }
+
+    #[test]
+    fn test_stopwatch_counts_up() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::stopwatch_with_clock(clock.clone());
+        assert_eq!(timer.mode(), TimerMode::Stopwatch);
+        assert_eq!(timer.get_display_time_string(), "00:00:00");
+        
+        // Start, run for a while, pause
+        timer.start_timer();
+        assert!(timer.is_running());
+        clock.advance(Duration::from_millis(61_900));
+        assert!(!timer.tick());
+        assert_eq!(timer.get_display_time_string(), "00:01:01");
+        timer.pause_timer();
+        
+        // Paused time isn't counted
+        clock.advance(Duration::from_secs(30));
+        timer.tick();
+        assert_eq!(timer.get_state().elapsed, Duration::from_millis(61_900));
+        
+        // Resume keeps counting well past any countdown limit, never completing
+        timer.start_timer();
+        clock.advance(Duration::from_secs(48 * 3600));
+        assert!(!timer.tick());
+        assert!(timer.is_running());
+        assert!(!timer.is_completed());
+        assert_eq!(timer.get_display_time_string(), "2:00:01:01");
+        
+        // Reset returns to zero and stays a stopwatch
+        timer.reset_timer();
+        assert!(!timer.is_running());
+        assert_eq!(timer.mode(), TimerMode::Stopwatch);
+        assert_eq!(timer.get_display_time_string(), "00:00:00");
+    }
+
+    #[test]
+    fn test_switching_modes() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 5, 0, clock.clone()).unwrap();
+        timer.start_timer();
+        
+        timer.set_mode(TimerMode::Stopwatch);
+        assert!(!timer.is_running());
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+        
+        // A countdown has nothing to count down until set_time is called
+        timer.set_mode(TimerMode::Countdown);
+        timer.start_timer();
+        assert!(!timer.is_running());
+        
+        timer.set_mode(TimerMode::Stopwatch);
+        timer.set_time(0, 0, 10).unwrap();
+        assert_eq!(timer.mode(), TimerMode::Countdown);
+        assert_eq!(timer.get_display_time_string(), "00:00:10");
+    }
+
+    #[test]
+    fn test_lap_records_elapsed_and_split() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::stopwatch_with_clock(clock.clone());
+        
+        // No laps before the timer runs
+        assert_eq!(timer.lap(), None);
+        
+        timer.start_timer();
+        clock.advance(Duration::from_secs(40));
+        let first = timer.lap().unwrap();
+        assert_eq!(first, Lap { number: 1, elapsed: Duration::from_secs(40), split: Duration::from_secs(40) });
+        
+        // Paused time is excluded from the next split
+        clock.advance(Duration::from_secs(5));
+        timer.pause_timer();
+        assert_eq!(timer.lap(), None);
+        clock.advance(Duration::from_secs(60));
+        timer.start_timer();
+        clock.advance(Duration::from_secs(30));
+        let second = timer.lap().unwrap();
+        assert_eq!(second, Lap { number: 2, elapsed: Duration::from_secs(75), split: Duration::from_secs(35) });
+        
+        assert_eq!(timer.get_state().laps, vec![first, second]);
+        assert_eq!(timer.get_state().fastest_lap(), Some(&second));
+        
+        timer.reset_timer();
+        assert!(timer.get_state().laps.is_empty());
+    }
+
+    #[test]
+    fn test_countdown_laps_reach_callback() {
+        use std::sync::{Arc, Mutex};
+        
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 1, 0, clock.clone()).unwrap();
+        let last_state = Arc::new(Mutex::new(None));
+        let last_state_clone = last_state.clone();
+        timer.set_state_callback(move |state| {
+            *last_state_clone.lock().unwrap() = Some(state);
+        });
+        
+        timer.start_timer();
+        clock.advance(Duration::from_millis(12_500));
+        timer.lap();
+        
+        let state = last_state.lock().unwrap().clone().unwrap();
+        assert_eq!(state.laps.len(), 1);
+        assert_eq!(state.laps[0].split, Duration::from_millis(12_500));
+        assert_eq!(state.remaining, Duration::from_millis(47_500));
+    }
+
+    #[test]
+    fn test_overtime_counts_past_zero() {
+        use std::sync::{Arc, Mutex};
+        
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 10, clock.clone()).unwrap();
+        timer.set_overtime(true);
+        let completions = Arc::new(Mutex::new(0));
+        let completions_clone = completions.clone();
+        timer.set_state_callback(move |state| {
+            if state.is_completed && !state.is_overtime() && state.remaining.is_zero() {
+                *completions_clone.lock().unwrap() += 1;
+            }
+        });
+        
+        // Reaching zero fires completion but keeps running
+        timer.start_timer();
+        clock.advance(Duration::from_secs(10));
+        assert!(timer.tick());
+        assert!(timer.is_completed());
+        assert!(timer.is_running());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:00");
+        
+        // Keeps counting up as negative time, completing only once
+        clock.advance(Duration::from_secs(133));
+        assert!(!timer.tick());
+        assert_eq!(timer.get_state().overtime, Duration::from_secs(133));
+        assert_eq!(timer.get_remaining_time_string(), "-00:02:13");
+        assert_eq!(*completions.lock().unwrap(), 1);
+        
+        // Can be paused and resumed while in overtime
+        timer.pause_timer();
+        clock.advance(Duration::from_secs(60));
+        timer.start_timer();
+        assert!(timer.is_running());
+        clock.advance(Duration::from_secs(1));
+        timer.tick();
+        assert_eq!(timer.get_remaining_time_string(), "-00:02:14");
+        
+        // Only a reset stops it
+        timer.reset_timer();
+        assert!(!timer.is_running());
+        assert!(!timer.is_completed());
+        assert_eq!(timer.get_remaining_time_string(), "00:00:10");
+    }
+
+    #[test]
+    fn test_overtime_disabled_stops_at_zero() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 10, clock.clone()).unwrap();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(15));
+        assert!(timer.tick());
+        assert!(!timer.is_running());
+        assert!(!timer.get_state().is_overtime());
+        
+        // Completed countdowns can't be restarted without a reset
+        timer.start_timer();
+        assert!(!timer.is_running());
+        
+        // Turning overtime off mid-overtime stops the timer
+        timer.reset_timer();
+        timer.set_overtime(true);
+        timer.start_timer();
+        clock.advance(Duration::from_secs(15));
+        timer.tick();
+        assert!(timer.is_running());
+        timer.set_overtime(false);
+        assert!(!timer.is_running());
+        assert!(timer.is_completed());
+    }
+
+    #[test]
+    fn test_set_duration() {
+        let mut timer = TimerLogic::new();
+        assert!(timer.set_duration(Duration::from_millis(5_025_900)).is_ok());
+        assert_eq!(timer.get_remaining_time_string(), "01:23:45");
+        
+        assert_eq!(timer.set_duration(Duration::from_millis(999)), Err(TimerError::ZeroDuration));
+        assert_eq!(
+            timer.set_duration(Duration::from_secs(100 * 86_400)),
+            Err(TimerError::DaysOutOfRange(100))
+        );
+        assert_eq!(timer.get_remaining_time_string(), "01:23:45");
+    }
+
+    #[test]
+    fn test_target_time_counts_down_on_wall_clock() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        timer.set_target_time(NaiveTime::from_hms_opt(9, 30, 0).unwrap()).unwrap();
+        let target = clock.wall_time() + TimeDelta::minutes(30);
+        assert_eq!(timer.get_state().target, Some(target));
+        assert_eq!(timer.get_remaining_time_string(), "00:30:00");
+        
+        timer.start_timer();
+        clock.advance(Duration::from_secs(600));
+        assert!(!timer.tick());
+        assert_eq!(timer.get_remaining_time_string(), "00:20:00");
+        
+        clock.advance(Duration::from_secs(1200));
+        assert!(timer.tick());
+        assert!(timer.is_completed());
+        
+        // A fixed duration replaces the target
+        timer.set_time(0, 5, 0).unwrap();
+        assert_eq!(timer.get_state().target, None);
+    }
+
+    #[test]
+    fn test_target_time_crosses_midnight() {
+        let clock = MockClock::new();
+        let late = clock.wall_time().with_time(NaiveTime::from_hms_opt(23, 50, 0).unwrap()).unwrap();
+        clock.set_wall_time(late);
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        
+        timer.set_target_time(NaiveTime::from_hms_opt(0, 10, 0).unwrap()).unwrap();
+        assert_eq!(timer.get_state().target, Some(late + TimeDelta::minutes(20)));
+        assert_eq!(timer.get_remaining_time_string(), "00:20:00");
+        
+        // The current time means the same time tomorrow
+        timer.set_target_time(NaiveTime::from_hms_opt(23, 50, 0).unwrap()).unwrap();
+        assert_eq!(timer.get_remaining_time_string(), "1:00:00:00");
+        
+        let past = clock.wall_time() - TimeDelta::minutes(1);
+        assert_eq!(timer.set_target(past), Err(TimerError::TargetInPast(past)));
+    }
+
+    /// MockClock in a zone that goes from UTC+0 to UTC+1 at 02:00 on
+    /// 2024-01-02 and back at 03:00 on 2024-01-03
+    struct DaylightSavingClock(MockClock);
+
+    impl Clock for DaylightSavingClock {
+        fn now(&self) -> Instant {
+            self.0.now()
+        }
+
+        fn wall_time(&self) -> DateTime<FixedOffset> {
+            let utc = self.0.wall_time().naive_utc();
+            let summer = utc >= at(2, 2) && utc < at(3, 2);
+            utc.and_utc().with_timezone(&offset(summer))
+        }
+
+        fn local_datetime(&self, local: NaiveDateTime) -> LocalResult<DateTime<FixedOffset>> {
+            let resolve = |summer| offset(summer).from_local_datetime(&local).unwrap();
+            if local < at(2, 2) || local >= at(3, 3) {
+                LocalResult::Single(resolve(false))
+            } else if local < at(2, 3) {
+                LocalResult::None
+            } else if local < at(3, 2) {
+                LocalResult::Single(resolve(true))
+            } else {
+                LocalResult::Ambiguous(resolve(true), resolve(false))
+            }
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 1, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn offset(summer: bool) -> FixedOffset {
+        FixedOffset::east_opt(if summer { 3600 } else { 0 }).unwrap()
+    }
+
+    #[test]
+    fn test_target_time_across_daylight_saving_change() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(DaylightSavingClock(clock.clone()));
+        let hm = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+
+        // 08:00 tomorrow is 22 hours away once the clocks go forward
+        timer.set_target_time(hm(8, 0)).unwrap();
+        assert_eq!(timer.get_remaining_time_string(), "22:00:00");
+
+        // 02:30 tomorrow is skipped, so it counts to 03:30 summer time
+        clock.set_wall_time(at(1, 23).and_utc().fixed_offset());
+        timer.set_target_time(hm(2, 30)).unwrap();
+        assert_eq!(timer.get_state().target, Some(at(2, 2).and_utc().fixed_offset() + TimeDelta::minutes(30)));
+        assert_eq!(timer.get_remaining_time_string(), "03:30:00");
+
+        // 02:30 happens twice when the clocks go back; the first one counts
+        clock.set_wall_time(at(2, 22).and_utc().fixed_offset());
+        timer.set_target_time(hm(2, 30)).unwrap();
+        assert_eq!(timer.get_remaining_time_string(), "03:30:00");
+    }
+
+    #[test]
+    fn test_target_follows_system_clock_changes() {
+        let clock = MockClock::new();
+        let start = clock.wall_time();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        timer.set_overtime(true);
+        timer.set_target(start + TimeDelta::hours(1)).unwrap();
+        timer.start_timer();
+        
+        clock.set_wall_time(start + TimeDelta::minutes(45));
+        timer.tick();
+        assert_eq!(timer.get_remaining_time_string(), "00:15:00");
+        
+        clock.set_wall_time(start + TimeDelta::minutes(30));
+        timer.tick();
+        assert_eq!(timer.get_remaining_time_string(), "00:30:00");
+        
+        clock.set_wall_time(start + TimeDelta::minutes(62));
+        assert!(timer.tick());
+        assert!(timer.is_running());
+        assert_eq!(timer.get_remaining_time_string(), "-00:02:00");
+        
+        // Resetting keeps the target, which has now passed
+        timer.reset_timer();
+        assert_eq!(timer.get_remaining_time_string(), "00:00:00");
+        timer.start_timer();
+        assert!(!timer.is_running());
+    }
+}
@@ -0,0 +1,205 @@
+use std::rc::Rc;
+
+use crate::timer::{Clock, SystemClock, TimerError, TimerLogic};
+
+/// Identifier of a timer owned by a [`TimerManager`]
+///
+/// IDs are never reused within a manager, even after the timer is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimerId(pub u64);
+
+/// A timer owned by a [`TimerManager`] together with its ID and name
+pub struct NamedTimer {
+    /// Unique ID of the timer
+    pub id: TimerId,
+    /// User-facing name, e.g. "Oven"
+    pub name: String,
+    /// The timer itself
+    pub timer: TimerLogic,
+}
+
+/// Owns any number of named timers and ticks them together
+///
+/// Timers are kept in the order they were added.
+pub struct TimerManager {
+    timers: Vec<NamedTimer>,
+    next_id: u64,
+    clock: Rc<dyn Clock>,
+}
+
+impl TimerManager {
+    /// Creates an empty manager whose timers use the system clock
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    /// Creates an empty manager whose timers all read time from `clock`
+    pub fn with_clock<C: Clock + 'static>(clock: C) -> Self {
+        Self {
+            timers: Vec::new(),
+            next_id: 1,
+            clock: Rc::new(clock),
+        }
+    }
+
+    /// Adds a countdown timer with the given name and duration
+    pub fn add(
+        &mut self,
+        name: impl Into<String>,
+        hours: u32,
+        minutes: u32,
+        seconds: u32,
+    ) -> Result<TimerId, TimerError> {
+        let timer = TimerLogic::with_time_and_clock(hours, minutes, seconds, self.clock.clone())?;
+        Ok(self.insert(name.into(), timer))
+    }
+
+    /// Adds a countdown timer with the given name and a duration that may span days
+    pub fn add_with_days(
+        &mut self,
+        name: impl Into<String>,
+        days: u32,
+        hours: u32,
+        minutes: u32,
+        seconds: u32,
+    ) -> Result<TimerId, TimerError> {
+        let mut timer = TimerLogic::with_clock(self.clock.clone());
+        timer.set_time_with_days(days, hours, minutes, seconds)?;
+        Ok(self.insert(name.into(), timer))
+    }
+
+    /// Adds a stopwatch with the given name
+    pub fn add_stopwatch(&mut self, name: impl Into<String>) -> TimerId {
+        let timer = TimerLogic::stopwatch_with_clock(self.clock.clone());
+        self.insert(name.into(), timer)
+    }
+
+    /// Removes a timer, returning it if it existed
+    pub fn remove(&mut self, id: TimerId) -> Option<NamedTimer> {
+        let index = self.timers.iter().position(|named| named.id == id)?;
+        Some(self.timers.remove(index))
+    }
+
+    /// Renames a timer, returning false if it doesn't exist
+    pub fn rename(&mut self, id: TimerId, name: impl Into<String>) -> bool {
+        match self.find_mut(id) {
+            Some(named) => {
+                named.name = name.into();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Gets a timer by ID
+    pub fn get(&self, id: TimerId) -> Option<&TimerLogic> {
+        self.timers.iter().find(|named| named.id == id).map(|named| &named.timer)
+    }
+
+    /// Gets a timer by ID for starting, pausing or resetting it
+    pub fn get_mut(&mut self, id: TimerId) -> Option<&mut TimerLogic> {
+        self.find_mut(id).map(|named| &mut named.timer)
+    }
+
+    /// Gets every timer in the order they were added
+    pub fn timers(&self) -> &[NamedTimer] {
+        &self.timers
+    }
+
+    /// Gets the number of timers
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Checks if the manager has no timers
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Ticks every timer, returning the IDs of those that completed on this call
+    pub fn tick(&mut self) -> Vec<TimerId> {
+        self.timers
+            .iter_mut()
+            .filter_map(|named| named.timer.tick().then_some(named.id))
+            .collect()
+    }
+
+    fn insert(&mut self, name: String, timer: TimerLogic) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+        self.timers.push(NamedTimer { id, name, timer });
+        id
+    }
+
+    fn find_mut(&mut self, id: TimerId) -> Option<&mut NamedTimer> {
+        self.timers.iter_mut().find(|named| named.id == id)
+    }
+}
+
+impl Default for TimerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::MockClock;
+    use std::time::Duration;
+
+    #[test]
+    fn test_add_remove_and_rename() {
+        let mut manager = TimerManager::new();
+        let oven = manager.add("Oven", 0, 45, 0).unwrap();
+        let build = manager.add("Build", 0, 10, 0).unwrap();
+        assert!(manager.add("Broken", 0, 0, 0).is_err());
+        assert_eq!(manager.len(), 2);
+
+        let soak = manager.add_with_days("Soak", 3, 0, 0, 0).unwrap();
+        assert_eq!(manager.get(soak).unwrap().get_remaining_time_string(), "3:00:00:00");
+        assert!(manager.remove(soak).is_some());
+
+        assert!(manager.rename(build, "CI build"));
+        let names: Vec<&str> = manager.timers().iter().map(|named| named.name.as_str()).collect();
+        assert_eq!(names, ["Oven", "CI build"]);
+
+        assert!(manager.remove(oven).is_some());
+        assert!(manager.remove(oven).is_none());
+        assert!(manager.get(oven).is_none());
+        assert!(!manager.rename(oven, "Gone"));
+
+        // IDs are not reused after removal
+        let standup = manager.add("Standup", 0, 15, 0).unwrap();
+        assert_ne!(standup, oven);
+        assert_eq!(manager.len(), 2);
+    }
+
+    #[test]
+    fn test_ticks_timers_independently() {
+        let clock = MockClock::new();
+        let mut manager = TimerManager::with_clock(clock.clone());
+        let short = manager.add("Short", 0, 0, 10).unwrap();
+        let long = manager.add("Long", 0, 0, 30).unwrap();
+        let paused = manager.add("Paused", 0, 0, 5).unwrap();
+        let stopwatch = manager.add_stopwatch("Lap");
+
+        for id in [short, long, paused, stopwatch] {
+            manager.get_mut(id).unwrap().start_timer();
+        }
+        manager.get_mut(paused).unwrap().pause_timer();
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(manager.tick(), vec![short]);
+        assert_eq!(manager.get(long).unwrap().get_remaining_time_string(), "00:00:20");
+        assert_eq!(manager.get(paused).unwrap().get_remaining_time_string(), "00:00:05");
+        assert_eq!(manager.get(stopwatch).unwrap().get_display_time_string(), "00:00:10");
+
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(manager.tick(), vec![long]);
+        assert!(manager.tick().is_empty());
+
+        manager.get_mut(short).unwrap().reset_timer();
+        assert!(!manager.get(short).unwrap().is_completed());
+    }
+}
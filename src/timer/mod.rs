@@ -1,5 +1,46 @@
+//! Countdown timer state and the logic that drives it.
+
+mod clock;
+#[cfg(feature = "config")]
+mod config;
+mod error;
+mod export;
+mod history;
+mod import;
+#[cfg(feature = "serde")]
+mod json;
 pub mod state;
 pub mod logic;
+mod manager;
+mod parse;
+mod persist;
+mod pomodoro;
+mod preset;
+mod sequence;
+mod stats;
+#[cfg(feature = "sqlite")]
+mod storage;
 
-pub use state::TimerState;
-pub use logic::TimerLogic;
\ No newline at end of file
+pub use clock::{Clock, MockClock, SystemClock};
+#[cfg(feature = "config")]
+pub use config::{
+    config_dir, Config, ConfigFile, HookEvent, HookSettings, SoundSettings, ThemeColors, ThemeName,
+    ThemeSettings, TimerDefaults, WindowSettings,
+};
+pub use error::{StorageError, TimerError};
+pub use export::{export_csv, export_history, export_ics, export_presets_csv, ExportFormat};
+pub use import::{import_presets, import_sessions, ImportFormat, ImportReport};
+pub use history::{decode_record, encode_record, HistoryStore, SessionOutcome, SessionRecord};
+#[cfg(feature = "serde")]
+pub use json::{from_json, to_json, Versioned, JSON_VERSION};
+pub use state::{DisplayPrecision, Lap, TimerMode, TimerState};
+pub use logic::{validate_duration, validate_time, validate_time_with_days, TimerLogic, MAX_DAYS};
+pub use manager::{NamedTimer, TimerId, TimerManager};
+pub use parse::{parse_duration, parse_time_of_day, parse_timer_input, TimerInput};
+pub use persist::{data_dir, decode_state, encode_state, StateStore};
+pub use pomodoro::{Pomodoro, PomodoroConfig, PomodoroPhase};
+pub use preset::{Preset, PresetList, PresetStore};
+pub use sequence::{ScheduledSegment, Segment, Sequence, SequenceEvent, SequenceRunner};
+pub use stats::{average_length, completion_rate, daily_totals, streaks, weekly_totals, PeriodTotal, Streaks};
+#[cfg(feature = "sqlite")]
+pub use storage::{Database, SessionQuery, SCHEMA_VERSION};
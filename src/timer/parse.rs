@@ -0,0 +1,334 @@
+use std::time::Duration;
+
+use chrono::NaiveTime;
+
+use crate::timer::TimerError;
+
+/// What a free-form timer entry asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerInput {
+    /// Count down for a duration, e.g. `1h30m`
+    Duration(Duration),
+    /// Count down until the wall clock shows a time of day, e.g. `until 14:30`
+    Until(NaiveTime),
+}
+
+/// Parses a quick-entry string into a duration or, when prefixed with
+/// `until` or `at`, a time of day to count down to
+///
+/// See [`parse_duration`] and [`parse_time_of_day`] for the accepted forms.
+pub fn parse_timer_input(input: &str) -> Result<TimerInput, TimerError> {
+    let trimmed = input.trim();
+    for prefix in ["until ", "at "] {
+        if trimmed.get(..prefix.len()).is_some_and(|head| head.eq_ignore_ascii_case(prefix)) {
+            return parse_time_of_day(&trimmed[prefix.len()..]).map(TimerInput::Until);
+        }
+    }
+    parse_duration(trimmed).map(TimerInput::Duration)
+}
+
+/// Parses a time of day such as `14:30`, `14:30:15`, `2:30pm` or `9 am`
+pub fn parse_time_of_day(input: &str) -> Result<NaiveTime, TimerError> {
+    let trimmed = input.trim();
+    let invalid = || TimerError::InvalidTimeOfDay(trimmed.to_string());
+    let text = trimmed.to_lowercase();
+
+    let (clock, meridiem) = if let Some(clock) = text.strip_suffix("am") {
+        (clock.trim_end(), Some(0))
+    } else if let Some(clock) = text.strip_suffix("pm") {
+        (clock.trim_end(), Some(12))
+    } else {
+        (text.as_str(), None)
+    };
+
+    let parts: Vec<&str> = clock.split(':').collect();
+    let minimum_parts = if meridiem.is_some() { 1 } else { 2 };
+    if parts.len() < minimum_parts || parts.len() > 3 {
+        return Err(invalid());
+    }
+    let mut fields = [0u32; 3];
+    for (field, part) in fields.iter_mut().zip(&parts) {
+        if part.is_empty() || part.len() > 2 || !part.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        *field = part.parse().map_err(|_| invalid())?;
+    }
+
+    let [mut hour, minute, second] = fields;
+    if let Some(offset) = meridiem {
+        if !(1..=12).contains(&hour) {
+            return Err(invalid());
+        }
+        hour = hour % 12 + offset;
+    }
+    NaiveTime::from_hms_opt(hour, minute, second).ok_or_else(invalid)
+}
+
+/// Parses a human-friendly duration such as `1h30m`, `90s`, `1:30` or `2.5m`
+///
+/// Accepted forms, ignoring case and surrounding whitespace:
+/// * compound units like `1d 2h 30m 15s`, with decimals such as `2.5m` and
+///   long unit names such as `1 hour 30 minutes`; each unit may appear once
+/// * colon forms `M:SS`, `H:MM:SS` and `D:HH:MM:SS`, as shown by the display
+/// * a bare number, read as minutes, e.g. `25`
+///
+/// The result is not range checked; pass it to
+/// [`TimerLogic::set_duration`](crate::timer::TimerLogic::set_duration) or
+/// [`validate_duration`](crate::timer::validate_duration) for that.
+pub fn parse_duration(input: &str) -> Result<Duration, TimerError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(TimerError::EmptyDurationInput);
+    }
+
+    let invalid = || TimerError::InvalidDurationInput(trimmed.to_string());
+    let text = trimmed.to_lowercase();
+    let nanos = if text.contains(':') {
+        parse_colon_form(&text)
+    } else if text.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        scaled_nanos(&text, 60)
+    } else {
+        return parse_units(&text, trimmed);
+    };
+    nanos.and_then(nanos_to_duration).ok_or_else(invalid)
+}
+
+/// Parses `M:SS`, `H:MM:SS` or `D:HH:MM:SS` into nanoseconds
+fn parse_colon_form(text: &str) -> Option<u128> {
+    let parts: Vec<&str> = text.split(':').map(str::trim).collect();
+    let (scales, limits): (&[u128], &[u128]) = match parts.len() {
+        2 => (&[60, 1], &[60]),
+        3 => (&[3600, 60, 1], &[60, 60]),
+        4 => (&[86_400, 3600, 60, 1], &[24, 60, 60]),
+        _ => return None,
+    };
+
+    let mut total: u128 = 0;
+    for (index, (part, scale)) in parts.iter().zip(scales).enumerate() {
+        if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let value: u128 = part.parse().ok()?;
+        // Only the leading field may exceed its usual range, e.g. `90:00`
+        if index > 0 && value >= limits[index - 1] {
+            return None;
+        }
+        total = total.checked_add(value.checked_mul(scale * 1_000_000_000)?)?;
+    }
+    Some(total)
+}
+
+/// Parses a sequence of `<number><unit>` pairs into a [`Duration`]
+fn parse_units(text: &str, original: &str) -> Result<Duration, TimerError> {
+    let invalid = || TimerError::InvalidDurationInput(original.to_string());
+    let mut rest = text.trim_start();
+    let mut seen = Vec::new();
+    let mut total: u128 = 0;
+
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        if number_len == 0 {
+            return Err(invalid());
+        }
+        let (number, after) = rest.split_at(number_len);
+        let after = after.trim_start();
+
+        let unit_len = after.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+        let scale = match unit {
+            "d" | "day" | "days" => 86_400,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1,
+            "" => return Err(invalid()),
+            unknown => return Err(TimerError::UnknownDurationUnit(unknown.to_string())),
+        };
+        if seen.contains(&scale) {
+            return Err(invalid());
+        }
+        seen.push(scale);
+
+        let nanos = scaled_nanos(number, scale).ok_or_else(invalid)?;
+        total = total.checked_add(nanos).ok_or_else(invalid)?;
+        rest = after.trim_start();
+    }
+
+    nanos_to_duration(total).ok_or_else(invalid)
+}
+
+/// Converts a decimal number of `scale`-second units to nanoseconds, exactly
+/// up to nanosecond resolution
+fn scaled_nanos(number: &str, scale: u128) -> Option<u128> {
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let unit_nanos = scale * 1_000_000_000;
+    let whole: u128 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let mut nanos = whole.checked_mul(unit_nanos)?;
+
+    // Digits past nanosecond resolution cannot change the result
+    let fraction = &fraction[..fraction.len().min(9)];
+    if !fraction.is_empty() {
+        let digits: u128 = fraction.parse().ok()?;
+        nanos = nanos.checked_add(digits * unit_nanos / 10u128.pow(fraction.len() as u32))?;
+    }
+    Some(nanos)
+}
+
+fn nanos_to_duration(nanos: u128) -> Option<Duration> {
+    let seconds = u64::try_from(nanos / 1_000_000_000).ok()?;
+    Some(Duration::new(seconds, (nanos % 1_000_000_000) as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::DisplayPrecision;
+    use proptest::prelude::*;
+
+    fn secs(seconds: u64) -> Result<Duration, TimerError> {
+        Ok(Duration::from_secs(seconds))
+    }
+
+    #[test]
+    fn test_compound_units() {
+        assert_eq!(parse_duration("1h30m"), secs(5400));
+        assert_eq!(parse_duration("90s"), secs(90));
+        assert_eq!(parse_duration("1d 2h 3m 4s"), secs(93_784));
+        assert_eq!(parse_duration("  1 Hour 30 MINUTES "), secs(5400));
+        assert_eq!(parse_duration("2hrs15mins"), secs(8100));
+        assert_eq!(parse_duration("3 days"), secs(259_200));
+    }
+
+    #[test]
+    fn test_decimal_units() {
+        assert_eq!(parse_duration("2.5m"), secs(150));
+        assert_eq!(parse_duration(".5h"), secs(1800));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("0.1s"), Ok(Duration::from_millis(100)));
+        assert_eq!(parse_duration("1.0000000001s"), secs(1));
+    }
+
+    #[test]
+    fn test_colon_forms() {
+        assert_eq!(parse_duration("1:30"), secs(90));
+        assert_eq!(parse_duration("1:02:03"), secs(3723));
+        assert_eq!(parse_duration("2:00:00:00"), secs(172_800));
+        assert_eq!(parse_duration("90:00"), secs(5400));
+        assert_eq!(parse_duration("0:05"), secs(5));
+    }
+
+    #[test]
+    fn test_bare_number_is_minutes() {
+        assert_eq!(parse_duration("25"), secs(1500));
+        assert_eq!(parse_duration("1.5"), secs(90));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(parse_duration(""), Err(TimerError::EmptyDurationInput));
+        assert_eq!(parse_duration("   "), Err(TimerError::EmptyDurationInput));
+        assert_eq!(parse_duration("5 fortnights"), Err(TimerError::UnknownDurationUnit("fortnights".to_string())));
+
+        for input in [
+            "h", "1h30", "1h 1h", "1..5m", ".m", "-5m", "1:", ":30", "1:60", "1:2:3:4:5",
+            "1:75:00", "1:24:00:00", "1: 3x", "1h-30m", "99999999999999999999999999999999999999999d",
+        ] {
+            assert_eq!(
+                parse_duration(input),
+                Err(TimerError::InvalidDurationInput(input.trim().to_string())),
+                "{input:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        let time = |h, m, s| Ok(NaiveTime::from_hms_opt(h, m, s).unwrap());
+        assert_eq!(parse_time_of_day("14:30"), time(14, 30, 0));
+        assert_eq!(parse_time_of_day("00:05:09"), time(0, 5, 9));
+        assert_eq!(parse_time_of_day("2:30pm"), time(14, 30, 0));
+        assert_eq!(parse_time_of_day("9 AM"), time(9, 0, 0));
+        assert_eq!(parse_time_of_day("12am"), time(0, 0, 0));
+        assert_eq!(parse_time_of_day("12:15 pm"), time(12, 15, 0));
+
+        for input in ["14", "24:00", "12:60", "13pm", "0am", "1:2:3:4", "14:", "abc", "123:00"] {
+            assert_eq!(
+                parse_time_of_day(input),
+                Err(TimerError::InvalidTimeOfDay(input.to_string())),
+                "{input:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_timer_input() {
+        assert_eq!(parse_timer_input("25m"), Ok(TimerInput::Duration(Duration::from_secs(1500))));
+        assert_eq!(
+            parse_timer_input(" Until 16:45"),
+            Ok(TimerInput::Until(NaiveTime::from_hms_opt(16, 45, 0).unwrap()))
+        );
+        assert_eq!(
+            parse_timer_input("at 10am"),
+            Ok(TimerInput::Until(NaiveTime::from_hms_opt(10, 0, 0).unwrap()))
+        );
+        assert!(parse_timer_input("until later").is_err());
+    }
+
+    #[test]
+    fn test_error_messages() {
+        assert_eq!(
+            parse_duration("1h 1h").unwrap_err().to_string(),
+            "Cannot read \"1h 1h\" as a duration, try e.g. 1h30m, 90s or 1:30"
+        );
+        assert_eq!(
+            parse_duration("5 parsecs").unwrap_err().to_string(),
+            "Unknown duration unit \"parsecs\", use d, h, m or s"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_compound_units_add_up(days in 0u64..100, hours in 0u64..24, minutes in 0u64..60, seconds in 0u64..60) {
+            let input = format!("{days}d {hours}h {minutes}m {seconds}s");
+            let expected = days * 86_400 + hours * 3600 + minutes * 60 + seconds;
+            prop_assert_eq!(parse_duration(&input), secs(expected));
+        }
+
+        #[test]
+        fn prop_display_format_round_trips(seconds in 1u64..100 * 86_400) {
+            let display = DisplayPrecision::Seconds.format(Duration::from_secs(seconds));
+            prop_assert_eq!(parse_duration(&display), secs(seconds));
+        }
+
+        #[test]
+        fn prop_decimal_minutes_match_seconds(tenths in 0u64..100_000) {
+            let input = format!("{}.{}m", tenths / 10, tenths % 10);
+            prop_assert_eq!(parse_duration(&input), secs(tenths * 6));
+        }
+
+        #[test]
+        fn prop_never_panics(input in "\\PC*") {
+            let _ = parse_duration(&input);
+            let _ = parse_timer_input(&input);
+        }
+
+        #[test]
+        fn prop_time_of_day_round_trips(hour in 0u32..24, minute in 0u32..60) {
+            let time = NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+            prop_assert_eq!(parse_time_of_day(&time.format("%H:%M").to_string()), Ok(time));
+            prop_assert_eq!(parse_time_of_day(&time.format("%-I:%M%P").to_string()), Ok(time));
+        }
+
+        #[test]
+        fn prop_rejects_out_of_range_colon_fields(minutes in 0u64..1000, seconds in 60u64..1000) {
+            let input = format!("{minutes}:{seconds}");
+            prop_assert!(parse_duration(&input).is_err());
+        }
+    }
+}
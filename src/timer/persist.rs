@@ -0,0 +1,400 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::DateTime;
+
+use crate::timer::{validate_time_with_days, Lap, TimerError, TimerMode, TimerState};
+
+/// Version written to the first line of every state file
+const STATE_FILE_VERSION: u32 = 1;
+
+/// Gets the directory the application keeps its data in, following the XDG
+/// base directory spec: `$XDG_DATA_HOME/rust-slint-timer`, falling back to
+/// `~/.local/share/rust-slint-timer`
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".local").join("share"),
+    };
+    Some(base.join("rust-slint-timer"))
+}
+
+/// Encodes a state as `key=value` lines for a state file
+///
+/// Elapsed, remaining and overtime are left out while running, because they
+/// are recovered from `started_at` on restore. This keeps the file unchanged
+/// between the state changes that matter. Line breaks in the label are
+/// replaced with spaces.
+pub fn encode_state(state: &TimerState) -> String {
+    let mut lines = vec![
+        format!("version={}", STATE_FILE_VERSION),
+        format!("mode={}", state.mode.key()),
+        format!("days={}", state.days),
+        format!("hours={}", state.hours),
+        format!("minutes={}", state.minutes),
+        format!("seconds={}", state.seconds),
+        format!("running={}", state.is_running),
+        format!("completed={}", state.is_completed),
+        format!("overtime={}", state.overtime_enabled),
+    ];
+    if let Some(label) = &state.label {
+        lines.push(format!("label={}", label.replace(['\n', '\r'], " ")));
+    }
+    if let Some(target) = state.target {
+        lines.push(format!("target={}", target.to_rfc3339()));
+    }
+    match state.started_at {
+        Some(started_at) if state.is_running => lines.push(format!("started_at={}", started_at.to_rfc3339())),
+        _ => {
+            lines.push(format!("elapsed_ns={}", state.elapsed.as_nanos()));
+            lines.push(format!("remaining_ns={}", state.remaining.as_nanos()));
+            lines.push(format!("overtime_ns={}", state.overtime.as_nanos()));
+        }
+    }
+    for lap in &state.laps {
+        lines.push(format!("lap={},{},{}", lap.number, lap.elapsed.as_nanos(), lap.split.as_nanos()));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Decodes a state written by [`encode_state`]
+pub fn decode_state(text: &str) -> Result<TimerState, TimerError> {
+    let mut state = TimerState::default();
+    let mut version = None;
+    let mut overtime_enabled = None;
+    // Lines the days, hours, minutes and seconds were read from
+    let mut time_lines = [1; 4];
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let invalid = |reason: &str| TimerError::InvalidSavedState {
+            line: line_number,
+            reason: reason.to_string(),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or_else(|| invalid("expected key=value"))?;
+        let number = || value.parse::<u32>().map_err(|_| invalid("expected a whole number"));
+        let flag = || value.parse::<bool>().map_err(|_| invalid("expected true or false"));
+        let nanos = |value: &str| {
+            value
+                .parse::<u64>()
+                .map(Duration::from_nanos)
+                .map_err(|_| invalid("expected a number of nanoseconds"))
+        };
+        let time = || DateTime::parse_from_rfc3339(value).map_err(|_| invalid("expected an RFC 3339 time"));
+
+        match key {
+            "version" => {
+                if number()? != STATE_FILE_VERSION {
+                    return Err(invalid("unsupported version"));
+                }
+                version = Some(STATE_FILE_VERSION);
+            }
+            "mode" => state.mode = TimerMode::from_key(value).ok_or_else(|| invalid("expected countdown or stopwatch"))?,
+            "days" => (state.days, time_lines[0]) = (number()?, line_number),
+            "hours" => (state.hours, time_lines[1]) = (number()?, line_number),
+            "minutes" => (state.minutes, time_lines[2]) = (number()?, line_number),
+            "seconds" => (state.seconds, time_lines[3]) = (number()?, line_number),
+            "running" => state.is_running = flag()?,
+            "completed" => state.is_completed = flag()?,
+            "overtime" => overtime_enabled = Some(flag()?),
+            "label" => state.label = (!value.is_empty()).then(|| value.to_string()),
+            "target" => state.target = Some(time()?),
+            "started_at" => state.started_at = Some(time()?),
+            "elapsed_ns" => state.elapsed = nanos(value)?,
+            "remaining_ns" => state.remaining = nanos(value)?,
+            "overtime_ns" => state.overtime = nanos(value)?,
+            "lap" => {
+                let fields: Vec<&str> = value.split(',').collect();
+                let [number, elapsed, split] = fields[..] else {
+                    return Err(invalid("expected number,elapsed_ns,split_ns"));
+                };
+                state.laps.push(Lap {
+                    number: number.parse().map_err(|_| invalid("expected a lap number"))?,
+                    elapsed: nanos(elapsed)?,
+                    split: nanos(split)?,
+                });
+            }
+            _ => return Err(invalid("unknown key")),
+        }
+    }
+
+    if version.is_none() {
+        return Err(TimerError::InvalidSavedState { line: 1, reason: "missing version".to_string() });
+    }
+    // A zero duration is fine: stopwatches and unset countdowns have one
+    if let Err(error) = validate_time_with_days(state.days, state.hours, state.minutes, state.seconds)
+        && error != TimerError::ZeroDuration
+    {
+        let line = match error {
+            TimerError::DaysOutOfRange(_) => time_lines[0],
+            TimerError::HoursOutOfRange(_) => time_lines[1],
+            TimerError::MinutesOutOfRange(_) => time_lines[2],
+            _ => time_lines[3],
+        };
+        return Err(TimerError::InvalidSavedState { line, reason: error.to_string() });
+    }
+    // Files saved before the flag was written only show it by being in overtime
+    state.overtime_enabled = overtime_enabled.unwrap_or_else(|| state.is_overtime());
+    if state.is_running && state.started_at.is_none() {
+        return Err(TimerError::InvalidSavedState {
+            line: text.lines().count(),
+            reason: "running state without started_at".to_string(),
+        });
+    }
+    Ok(state)
+}
+
+/// A file the current timer state is saved to and restored from
+///
+/// Saving skips the write when the encoded state hasn't changed since the
+/// last save, so it is cheap to call from a state callback that fires on
+/// every tick.
+pub struct StateStore {
+    path: PathBuf,
+    last_saved: Mutex<Option<String>>,
+}
+
+impl StateStore {
+    /// Creates a store for the state file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            last_saved: Mutex::new(None),
+        }
+    }
+
+    /// Creates a store for the file `state` in the application's [`data_dir`]
+    pub fn in_data_dir() -> Option<Self> {
+        Some(Self::new(data_dir()?.join("state")))
+    }
+
+    /// Gets the path of the state file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the saved state, or `None` if nothing has been saved yet
+    pub fn load(&self) -> io::Result<Option<TimerState>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let state = decode_state(&text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        *self.last_saved.lock().unwrap() = Some(text);
+        Ok(Some(state))
+    }
+
+    /// Saves `state`, replacing the file atomically so a crash mid-write
+    /// never leaves a truncated state behind
+    pub fn save(&self, state: &TimerState) -> io::Result<()> {
+        let text = encode_state(state);
+        let mut last_saved = self.last_saved.lock().unwrap();
+        if last_saved.as_deref() == Some(text.as_str()) {
+            return Ok(());
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = self.path.with_extension("partial");
+        fs::write(&partial, &text)?;
+        fs::rename(&partial, &self.path)?;
+        *last_saved = Some(text);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::{Clock, MockClock, TimerLogic};
+    use chrono::TimeDelta;
+    use std::sync::Arc;
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("rust-slint-timer-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::stopwatch_with_clock(clock.clone());
+        timer.start_timer();
+        clock.advance(Duration::from_millis(1500));
+        timer.lap();
+        clock.advance(Duration::from_secs(2));
+        timer.pause_timer();
+
+        let state = timer.get_state().clone();
+        let decoded = decode_state(&encode_state(&state)).unwrap();
+        assert_eq!(decoded.mode, TimerMode::Stopwatch);
+        assert_eq!(decoded.elapsed, Duration::from_millis(3500));
+        assert_eq!(decoded.laps, state.laps);
+        assert!(!decoded.is_running);
+
+        let mut target = TimerLogic::with_clock(clock.clone());
+        target.set_target(clock.wall_time() + TimeDelta::hours(30)).unwrap();
+        let decoded = decode_state(&encode_state(target.get_state())).unwrap();
+        assert_eq!(decoded.target, target.get_state().target);
+        assert_eq!((decoded.days, decoded.hours), (1, 6));
+    }
+
+    #[test]
+    fn test_running_state_omits_derived_times() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 5, 0, clock.clone()).unwrap();
+        timer.start_timer();
+        let first = encode_state(timer.get_state());
+
+        clock.advance(Duration::from_secs(3));
+        timer.tick();
+        assert_eq!(encode_state(timer.get_state()), first);
+        assert!(first.contains("started_at=2024-01-01T09:00:00+00:00"));
+        assert!(!first.contains("elapsed_ns"));
+    }
+
+    #[test]
+    fn test_decode_reports_line_errors() {
+        assert_eq!(
+            decode_state("version=1\nhours=two\n").unwrap_err(),
+            TimerError::InvalidSavedState { line: 2, reason: "expected a whole number".to_string() }
+        );
+        assert_eq!(
+            decode_state("version=1\ncolour=red\n").unwrap_err().to_string(),
+            "Saved timer state is invalid at line 2: unknown key"
+        );
+        assert!(decode_state("hours=1\n").is_err());
+        assert!(decode_state("version=9\n").is_err());
+        assert!(decode_state("version=1\nrunning=true\n").is_err());
+        assert_eq!(
+            decode_state("version=1\nhours=1\nminutes=75\n").unwrap_err().to_string(),
+            "Saved timer state is invalid at line 3: Minutes must be between 0 and 59, got 75"
+        );
+        assert!(decode_state("version=1\ndays=100\n").is_err());
+        assert!(decode_state("version=1\nmode=stopwatch\n").is_ok());
+    }
+
+    #[test]
+    fn test_overtime_flag_round_trips() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 1, 0, clock.clone()).unwrap();
+        timer.set_overtime(true);
+        // Saved before the countdown reaches zero, so it can't be inferred
+        assert!(decode_state(&encode_state(timer.get_state())).unwrap().overtime_enabled);
+
+        timer.set_overtime(false);
+        assert!(!decode_state(&encode_state(timer.get_state())).unwrap().overtime_enabled);
+    }
+
+    #[test]
+    fn test_restored_run_keeps_its_label() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 1, 0, clock.clone()).unwrap();
+        timer.set_label(Some("Write\nreport".to_string()));
+        timer.start_timer();
+        let text = encode_state(timer.get_state());
+        assert!(text.contains("label=Write report\n"));
+
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&records);
+        let mut restored = TimerLogic::with_clock(clock.clone());
+        restored.set_session_callback(move |record| sink.lock().unwrap().push(record));
+        restored.restore_state(decode_state(&text).unwrap());
+        assert_eq!(restored.label(), Some("Write report"));
+
+        clock.advance(Duration::from_secs(60));
+        assert!(restored.tick());
+        assert_eq!(records.lock().unwrap()[0].label.as_deref(), Some("Write report"));
+    }
+
+    #[test]
+    fn test_restore_counts_time_while_closed() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 10, 0, clock.clone()).unwrap();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(60));
+        timer.tick();
+        let saved = decode_state(&encode_state(timer.get_state())).unwrap();
+
+        // Four minutes pass on the wall clock before the next launch
+        let relaunch = MockClock::new();
+        relaunch.set_wall_time(clock.wall_time() + TimeDelta::minutes(4));
+        let mut restored = TimerLogic::with_clock(relaunch.clone());
+        restored.restore_state(saved);
+        assert!(restored.is_running());
+        assert_eq!(restored.get_remaining_time_string(), "00:05:00");
+
+        relaunch.advance(Duration::from_secs(300));
+        assert!(restored.tick());
+        assert!(restored.is_completed());
+    }
+
+    #[test]
+    fn test_restore_keeps_paused_run_paused() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 1, 0, clock.clone()).unwrap();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(20));
+        timer.pause_timer();
+        let saved = decode_state(&encode_state(timer.get_state())).unwrap();
+
+        let relaunch = MockClock::new();
+        relaunch.advance(Duration::from_secs(3600));
+        let mut restored = TimerLogic::with_clock(relaunch.clone());
+        restored.restore_state(saved);
+        assert!(restored.is_paused());
+        assert_eq!(restored.get_remaining_time_string(), "00:00:40");
+
+        restored.start_timer();
+        relaunch.advance(Duration::from_secs(10));
+        restored.tick();
+        assert_eq!(restored.get_remaining_time_string(), "00:00:30");
+        assert_eq!(restored.elapsed(), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_restored_completed_run_is_not_recorded_again() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 30, clock.clone()).unwrap();
+        timer.start_timer();
+        clock.advance(Duration::from_secs(30));
+        assert!(timer.tick());
+        let saved = decode_state(&encode_state(timer.get_state())).unwrap();
+
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&records);
+        let mut restored = TimerLogic::with_clock(MockClock::new());
+        restored.set_session_callback(move |record| sink.lock().unwrap().push(record));
+        restored.restore_state(saved);
+        assert!(restored.is_completed());
+
+        restored.reset_timer();
+        assert!(records.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_store_saves_and_loads() {
+        let path = temp_path("store").join("state");
+        let store = StateStore::new(&path);
+        assert!(store.load().unwrap().is_none());
+
+        let state = TimerState::with_days(2, 3, 4, 5);
+        store.save(&state).unwrap();
+        let loaded = StateStore::new(&path).load().unwrap().unwrap();
+        assert_eq!(loaded.duration(), state.duration());
+
+        fs::write(&path, "garbage").unwrap();
+        assert_eq!(store.load().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
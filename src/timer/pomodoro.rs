@@ -0,0 +1,338 @@
+use std::time::Duration;
+
+use crate::timer::{validate_duration, TimerError, TimerLogic};
+
+/// Phase of a Pomodoro session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PomodoroPhase {
+    /// Focused work interval
+    Work,
+    /// Short break between work intervals
+    ShortBreak,
+    /// Long break after every `long_break_interval` work intervals
+    LongBreak,
+}
+
+impl PomodoroPhase {
+    /// Gets a human-readable name for the phase
+    pub fn label(self) -> &'static str {
+        match self {
+            PomodoroPhase::Work => "Work",
+            PomodoroPhase::ShortBreak => "Short Break",
+            PomodoroPhase::LongBreak => "Long Break",
+        }
+    }
+}
+
+/// Lengths and behaviour of a Pomodoro session
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PomodoroConfig {
+    /// Length of a work interval
+    pub work: Duration,
+    /// Length of a short break
+    pub short_break: Duration,
+    /// Length of a long break
+    pub long_break: Duration,
+    /// Number of work intervals before a long break
+    pub long_break_interval: u32,
+    /// Whether the next phase starts automatically or waits for `confirm()`
+    pub auto_advance: bool,
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            work: Duration::from_secs(25 * 60),
+            short_break: Duration::from_secs(5 * 60),
+            long_break: Duration::from_secs(15 * 60),
+            long_break_interval: 4,
+            auto_advance: true,
+        }
+    }
+}
+
+impl PomodoroConfig {
+    /// Validates every phase length as a timer duration
+    pub fn validate(&self) -> Result<(), TimerError> {
+        for phase in [PomodoroPhase::Work, PomodoroPhase::ShortBreak, PomodoroPhase::LongBreak] {
+            validate_duration(self.duration_of(phase)).map_err(|error| TimerError::InvalidPhaseLength {
+                phase,
+                source: Box::new(error),
+            })?;
+        }
+
+        if self.long_break_interval == 0 {
+            return Err(TimerError::ZeroLongBreakInterval);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the configured length of `phase`
+    pub fn duration_of(&self, phase: PomodoroPhase) -> Duration {
+        match phase {
+            PomodoroPhase::Work => self.work,
+            PomodoroPhase::ShortBreak => self.short_break,
+            PomodoroPhase::LongBreak => self.long_break,
+        }
+    }
+}
+
+/// Pomodoro workflow that drives a TimerLogic through work and break phases
+///
+/// The controller doesn't own the timer: the caller keeps its TimerLogic and
+/// calls [`tick`](Self::tick) on the controller instead of on the timer, so
+/// the same timer, callback and display code serve both plain countdowns and
+/// Pomodoro sessions.
+#[derive(Debug, Clone)]
+pub struct Pomodoro {
+    config: PomodoroConfig,
+    phase: PomodoroPhase,
+    completed_cycles: u32,
+    awaiting_confirmation: bool,
+}
+
+impl Pomodoro {
+    /// Creates a new Pomodoro session starting with a work phase
+    pub fn new(config: PomodoroConfig) -> Result<Self, TimerError> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            phase: PomodoroPhase::Work,
+            completed_cycles: 0,
+            awaiting_confirmation: false,
+        })
+    }
+
+    /// Gets the session configuration
+    pub fn config(&self) -> &PomodoroConfig {
+        &self.config
+    }
+
+    /// Gets the current phase
+    pub fn phase(&self) -> PomodoroPhase {
+        self.phase
+    }
+
+    /// Gets the number of work phases completed in this session
+    pub fn completed_cycles(&self) -> u32 {
+        self.completed_cycles
+    }
+
+    /// Checks if the current phase is loaded but waiting for `confirm()` to start
+    pub fn is_awaiting_confirmation(&self) -> bool {
+        self.awaiting_confirmation
+    }
+
+    /// Restarts the session and loads the first work phase into `timer`
+    ///
+    /// The timer is left stopped; start it to begin working.
+    pub fn begin(&mut self, timer: &mut TimerLogic) {
+        self.phase = PomodoroPhase::Work;
+        self.completed_cycles = 0;
+        self.awaiting_confirmation = false;
+        self.load_phase(timer);
+    }
+
+    /// Ticks `timer` and moves to the next phase when the current one completes
+    ///
+    /// With auto-advance, the next phase includes the time the completed one
+    /// ran past its end, so phases don't drift behind the clock, and every
+    /// phase that ended since the last call is passed through. Returns the
+    /// new phase if a transition happened on this call.
+    pub fn tick(&mut self, timer: &mut TimerLogic) -> Option<PomodoroPhase> {
+        if !timer.tick() {
+            return None;
+        }
+
+        loop {
+            let overrun = timer.overrun();
+            self.advance(timer, overrun);
+            if !self.config.auto_advance || !timer.tick() {
+                return Some(self.phase);
+            }
+        }
+    }
+
+    /// Ends the current phase early and moves to the next one
+    ///
+    /// Skipping a work phase doesn't count it as a completed cycle.
+    pub fn skip(&mut self, timer: &mut TimerLogic) -> PomodoroPhase {
+        self.phase = match self.phase {
+            PomodoroPhase::Work => PomodoroPhase::ShortBreak,
+            PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => PomodoroPhase::Work,
+        };
+        self.enter_phase(timer, Duration::ZERO);
+        self.phase
+    }
+
+    /// Starts the phase that is waiting for confirmation
+    pub fn confirm(&mut self, timer: &mut TimerLogic) {
+        if self.awaiting_confirmation {
+            self.awaiting_confirmation = false;
+            timer.start_timer();
+        }
+    }
+
+    /// Records the completed phase and loads the next one, `overrun` into it
+    fn advance(&mut self, timer: &mut TimerLogic, overrun: Duration) {
+        self.phase = match self.phase {
+            PomodoroPhase::Work => {
+                self.completed_cycles += 1;
+                if self.completed_cycles.is_multiple_of(self.config.long_break_interval) {
+                    PomodoroPhase::LongBreak
+                } else {
+                    PomodoroPhase::ShortBreak
+                }
+            }
+            PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => PomodoroPhase::Work,
+        };
+        self.enter_phase(timer, overrun);
+    }
+
+    /// Loads the current phase and starts it `elapsed` in, or waits for
+    /// confirmation
+    fn enter_phase(&mut self, timer: &mut TimerLogic, elapsed: Duration) {
+        self.load_phase(timer);
+        if self.config.auto_advance {
+            timer.start_timer_after(elapsed);
+        } else {
+            self.awaiting_confirmation = true;
+        }
+    }
+
+    fn load_phase(&self, timer: &mut TimerLogic) {
+        timer
+            .set_duration(self.config.duration_of(self.phase))
+            .expect("phase lengths are validated on construction");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::MockClock;
+
+    fn short_config() -> PomodoroConfig {
+        PomodoroConfig {
+            work: Duration::from_secs(25),
+            short_break: Duration::from_secs(5),
+            long_break: Duration::from_secs(15),
+            long_break_interval: 2,
+            auto_advance: true,
+        }
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = PomodoroConfig { work: Duration::ZERO, ..short_config() };
+        let error = Pomodoro::new(config).unwrap_err();
+        assert_eq!(
+            error,
+            TimerError::InvalidPhaseLength {
+                phase: PomodoroPhase::Work,
+                source: Box::new(TimerError::ZeroDuration),
+            }
+        );
+        assert!(error.to_string().contains("Work length is invalid"));
+
+        let config = PomodoroConfig { long_break_interval: 0, ..short_config() };
+        assert_eq!(Pomodoro::new(config).unwrap_err(), TimerError::ZeroLongBreakInterval);
+    }
+
+    #[test]
+    fn test_cycles_through_phases() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        let mut pomodoro = Pomodoro::new(short_config()).unwrap();
+
+        pomodoro.begin(&mut timer);
+        assert_eq!(pomodoro.phase(), PomodoroPhase::Work);
+        assert_eq!(timer.get_remaining_time_string(), "00:00:25");
+        assert!(!timer.is_running());
+
+        timer.start_timer();
+        clock.advance(Duration::from_secs(24));
+        assert_eq!(pomodoro.tick(&mut timer), None);
+
+        // Work -> short break, starting automatically
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(pomodoro.tick(&mut timer), Some(PomodoroPhase::ShortBreak));
+        assert_eq!(pomodoro.completed_cycles(), 1);
+        assert_eq!(timer.get_remaining_time_string(), "00:00:05");
+        assert!(timer.is_running());
+
+        // Short break -> work -> long break after the second cycle
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(pomodoro.tick(&mut timer), Some(PomodoroPhase::Work));
+        clock.advance(Duration::from_secs(25));
+        assert_eq!(pomodoro.tick(&mut timer), Some(PomodoroPhase::LongBreak));
+        assert_eq!(pomodoro.completed_cycles(), 2);
+        assert_eq!(timer.get_remaining_time_string(), "00:00:15");
+
+        clock.advance(Duration::from_secs(15));
+        assert_eq!(pomodoro.tick(&mut timer), Some(PomodoroPhase::Work));
+        assert_eq!(pomodoro.completed_cycles(), 2);
+    }
+
+    #[test]
+    fn test_late_tick_carries_over_phases() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        let mut pomodoro = Pomodoro::new(short_config()).unwrap();
+
+        pomodoro.begin(&mut timer);
+        timer.start_timer();
+
+        // A late tick starts the break 300ms in, so it still ends on time
+        clock.advance(Duration::from_millis(25_300));
+        assert_eq!(pomodoro.tick(&mut timer), Some(PomodoroPhase::ShortBreak));
+        clock.advance(Duration::from_millis(4_700));
+        assert_eq!(pomodoro.tick(&mut timer), Some(PomodoroPhase::Work));
+
+        // A stall past the end of a phase passes through it
+        clock.advance(Duration::from_secs(45));
+        assert_eq!(pomodoro.tick(&mut timer), Some(PomodoroPhase::Work));
+        assert_eq!(pomodoro.completed_cycles(), 2);
+        assert_eq!(timer.get_remaining_time_string(), "00:00:20");
+    }
+
+    #[test]
+    fn test_waits_for_confirmation() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        let config = PomodoroConfig { auto_advance: false, ..short_config() };
+        let mut pomodoro = Pomodoro::new(config).unwrap();
+
+        pomodoro.begin(&mut timer);
+        timer.start_timer();
+        clock.advance(Duration::from_secs(25));
+        assert_eq!(pomodoro.tick(&mut timer), Some(PomodoroPhase::ShortBreak));
+        assert!(pomodoro.is_awaiting_confirmation());
+        assert!(!timer.is_running());
+
+        // Time passing before confirmation doesn't eat into the break
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(pomodoro.tick(&mut timer), None);
+        assert_eq!(timer.get_remaining_time_string(), "00:00:05");
+
+        pomodoro.confirm(&mut timer);
+        assert!(!pomodoro.is_awaiting_confirmation());
+        assert!(timer.is_running());
+    }
+
+    #[test]
+    fn test_skip_does_not_count_cycle() {
+        let mut timer = TimerLogic::new();
+        let mut pomodoro = Pomodoro::new(short_config()).unwrap();
+        pomodoro.begin(&mut timer);
+
+        assert_eq!(pomodoro.skip(&mut timer), PomodoroPhase::ShortBreak);
+        assert_eq!(pomodoro.completed_cycles(), 0);
+        assert_eq!(pomodoro.skip(&mut timer), PomodoroPhase::Work);
+        assert_eq!(timer.get_remaining_time_string(), "00:00:25");
+    }
+}
@@ -0,0 +1,266 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::timer::{data_dir, export_presets_csv, import_presets, validate_duration, ImportFormat, TimerError, TimerLogic};
+
+/// A named countdown duration, e.g. "Tea" for 3 minutes
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Preset {
+    /// Name shown on the preset's button
+    pub name: String,
+    /// Countdown duration the preset sets
+    pub duration: Duration,
+}
+
+impl Preset {
+    /// Creates a preset, checking that `duration` is a valid timer duration
+    ///
+    /// Surrounding whitespace is trimmed from the name, which must not be empty.
+    pub fn new(name: impl Into<String>, duration: Duration) -> Result<Self, TimerError> {
+        let name = preset_name(name.into())?;
+        validate_duration(duration)?;
+        Ok(Self { name, duration })
+    }
+
+    /// Sets `timer` to count down the preset's duration, starting it right
+    /// away if `start` is set
+    pub fn apply(&self, timer: &mut TimerLogic, start: bool) -> Result<(), TimerError> {
+        timer.set_duration(self.duration)?;
+        if start {
+            timer.start_timer();
+        }
+        Ok(())
+    }
+}
+
+/// Gets the form of a preset name that names are compared by, so names
+/// that differ only in case clash
+pub(crate) fn name_key(name: &str) -> String {
+    name.to_lowercase()
+}
+
+fn preset_name(name: String) -> Result<String, TimerError> {
+    match name.trim() {
+        "" => Err(TimerError::EmptyPresetName),
+        trimmed if trimmed.len() == name.len() => Ok(name),
+        trimmed => Ok(trimmed.to_string()),
+    }
+}
+
+/// Ordered list of presets with unique names, ignoring case
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PresetList {
+    presets: Vec<Preset>,
+}
+
+impl PresetList {
+    /// Creates an empty list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a list from `presets`, keeping the first of any presets with the same name
+    pub fn from_presets(presets: impl IntoIterator<Item = Preset>) -> Self {
+        let mut list = Self::new();
+        for preset in presets {
+            // A duplicate is the only possible error, and dropping it is the point
+            let _ = list.add(preset);
+        }
+        list
+    }
+
+    /// Gets the presets in display order
+    pub fn presets(&self) -> &[Preset] {
+        &self.presets
+    }
+
+    /// Gets the number of presets
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    /// Checks if the list has no presets
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+
+    /// Finds a preset by name, ignoring case
+    pub fn find(&self, name: &str) -> Option<&Preset> {
+        self.position(name.trim()).map(|index| &self.presets[index])
+    }
+
+    /// Appends a preset, rejecting a name that is already taken
+    pub fn add(&mut self, preset: Preset) -> Result<(), TimerError> {
+        if self.position(&preset.name).is_some() {
+            return Err(TimerError::DuplicatePresetName(preset.name));
+        }
+        self.presets.push(preset);
+        Ok(())
+    }
+
+    /// Renames the preset at `index`, rejecting a name another preset has;
+    /// returns false if there is no preset at `index`
+    pub fn rename(&mut self, index: usize, name: impl Into<String>) -> Result<bool, TimerError> {
+        let name = preset_name(name.into())?;
+        if index >= self.presets.len() {
+            return Ok(false);
+        }
+        if self.position(&name).is_some_and(|other| other != index) {
+            return Err(TimerError::DuplicatePresetName(name));
+        }
+        self.presets[index].name = name;
+        Ok(true)
+    }
+
+    /// Moves the preset at `from` to position `to`, shifting the ones in
+    /// between; returns false if either position is out of range
+    pub fn move_preset(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.presets.len() || to >= self.presets.len() {
+            return false;
+        }
+        let preset = self.presets.remove(from);
+        self.presets.insert(to, preset);
+        true
+    }
+
+    /// Removes the preset at `index`, returning it if it existed
+    pub fn remove(&mut self, index: usize) -> Option<Preset> {
+        (index < self.presets.len()).then(|| self.presets.remove(index))
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        let key = name_key(name);
+        self.presets.iter().position(|preset| name_key(&preset.name) == key)
+    }
+}
+
+/// A CSV file the presets are saved to, in the format
+/// [`import_presets`] reads, so it can be edited by hand or shared
+pub struct PresetStore {
+    path: PathBuf,
+}
+
+impl PresetStore {
+    /// Creates a store for the presets file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Creates a store for the file `presets.csv` in the application's [`data_dir`]
+    pub fn in_data_dir() -> Option<Self> {
+        Some(Self::new(data_dir()?.join("presets.csv")))
+    }
+
+    /// Gets the path of the presets file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the saved presets; a missing file is an empty list
+    ///
+    /// Fails on the first invalid entry rather than silently dropping presets.
+    pub fn load(&self) -> io::Result<PresetList> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(PresetList::new()),
+            Err(error) => return Err(error),
+        };
+        let report = import_presets(&text, ImportFormat::Csv, &[]);
+        if let Some(error) = report.errors.into_iter().next() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+        Ok(PresetList::from_presets(report.imported))
+    }
+
+    /// Saves `presets`, replacing the file atomically
+    pub fn save(&self, presets: &PresetList) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = self.path.with_extension("partial");
+        fs::write(&partial, export_presets_csv(presets.presets()))?;
+        fs::rename(&partial, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::MockClock;
+
+    fn preset(name: &str, minutes: u64) -> Preset {
+        Preset::new(name, Duration::from_secs(minutes * 60)).unwrap()
+    }
+
+    fn names(list: &PresetList) -> Vec<&str> {
+        list.presets().iter().map(|preset| preset.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_new_validates_duration() {
+        let tea = Preset::new("  Tea ", Duration::from_secs(180)).unwrap();
+        assert_eq!(tea.name, "Tea");
+        assert_eq!(tea.duration, Duration::from_secs(180));
+
+        assert_eq!(Preset::new("Nothing", Duration::ZERO).unwrap_err(), TimerError::ZeroDuration);
+        assert_eq!(Preset::new(" ", Duration::from_secs(60)).unwrap_err(), TimerError::EmptyPresetName);
+    }
+
+    #[test]
+    fn test_apply_sets_time_and_optionally_starts() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        preset("Standup", 15).apply(&mut timer, false).unwrap();
+        assert_eq!(timer.get_remaining_time_string(), "00:15:00");
+        assert!(!timer.is_running());
+
+        preset("Tea", 3).apply(&mut timer, true).unwrap();
+        assert_eq!(timer.get_remaining_time_string(), "00:03:00");
+        assert!(timer.is_running());
+    }
+
+    #[test]
+    fn test_create_rename_reorder_delete() {
+        let mut list = PresetList::new();
+        list.add(preset("Tea", 3)).unwrap();
+        list.add(preset("Standup", 15)).unwrap();
+        list.add(preset("Pasta", 10)).unwrap();
+        assert_eq!(list.add(preset("TEA", 4)), Err(TimerError::DuplicatePresetName("TEA".to_string())));
+
+        assert_eq!(list.rename(0, "Green tea"), Ok(true));
+        // Renaming a preset to its own name in another case is fine
+        assert_eq!(list.rename(1, "standup"), Ok(true));
+        assert_eq!(list.rename(2, "green TEA"), Err(TimerError::DuplicatePresetName("green TEA".to_string())));
+        assert_eq!(list.rename(9, "Soup"), Ok(false));
+
+        assert!(list.move_preset(2, 0));
+        assert!(!list.move_preset(0, 3));
+        assert_eq!(names(&list), ["Pasta", "Green tea", "standup"]);
+
+        assert_eq!(list.remove(1).unwrap().name, "Green tea");
+        assert!(list.remove(5).is_none());
+        assert_eq!(names(&list), ["Pasta", "standup"]);
+        assert_eq!(list.find("STANDUP").unwrap().duration, Duration::from_secs(900));
+        assert!(list.add(preset("ÉCLAIR", 1)).is_ok());
+        assert!(list.add(preset("éclair", 1)).is_err());
+    }
+
+    #[test]
+    fn test_store_saves_and_loads() {
+        let dir = std::env::temp_dir().join(format!("rust-slint-timer-presets-{}", std::process::id()));
+        let store = PresetStore::new(dir.join("presets.csv"));
+        assert!(store.load().unwrap().is_empty());
+
+        let list = PresetList::from_presets([preset("Tea, green", 3), preset("Work", 50 * 60), preset("tea, GREEN", 1)]);
+        assert_eq!(list.len(), 2);
+        store.save(&list).unwrap();
+        assert_eq!(store.load().unwrap(), list);
+
+        fs::write(store.path(), "name,duration\nTea,soon\n").unwrap();
+        assert_eq!(store.load().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
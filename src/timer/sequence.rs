@@ -0,0 +1,360 @@
+use std::time::Duration;
+
+use crate::timer::{validate_duration, TimerError, TimerLogic};
+
+/// A named, colored stretch of time in an interval program
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    /// Name shown and announced while the segment runs, e.g. "Work"
+    pub label: String,
+    /// Length of the segment
+    pub duration: Duration,
+    /// Display color as `0xRRGGBB`
+    pub color: u32,
+}
+
+impl Segment {
+    /// Creates a segment
+    pub fn new(label: impl Into<String>, duration: Duration, color: u32) -> Self {
+        Self {
+            label: label.into(),
+            duration,
+            color,
+        }
+    }
+}
+
+/// A segment as scheduled in a flattened program
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduledSegment {
+    /// The segment to run
+    pub segment: Segment,
+    /// 1-based round and total rounds, if the segment is part of a repeated block
+    pub round: Option<(u32, u32)>,
+}
+
+impl ScheduledSegment {
+    /// Describes the segment for announcements, e.g. "Work (round 3 of 8)"
+    pub fn announcement(&self) -> String {
+        match self.round {
+            Some((round, rounds)) => format!("{} (round {} of {})", self.segment.label, round, rounds),
+            None => self.segment.label.clone(),
+        }
+    }
+}
+
+/// An interval program made of single segments and repeated blocks
+///
+/// ```
+/// use std::time::Duration;
+/// use rust_slint_timer::timer::{Segment, Sequence};
+///
+/// let secs = Duration::from_secs;
+/// let program = Sequence::new()
+///     .segment(Segment::new("Warmup", secs(300), 0xffc107))
+///     .repeat(8, vec![
+///         Segment::new("Work", secs(20), 0xdc3545),
+///         Segment::new("Rest", secs(10), 0x28a745),
+///     ])
+///     .segment(Segment::new("Cooldown", secs(180), 0x17a2b8));
+///
+/// assert_eq!(program.schedule().len(), 18);
+/// assert_eq!(program.total_duration(), secs(720));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sequence {
+    schedule: Vec<ScheduledSegment>,
+}
+
+impl Sequence {
+    /// Creates an empty program
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the common warmup, `rounds` × (work, rest), cooldown program
+    ///
+    /// Warmup and cooldown are left out when their duration is zero.
+    pub fn interval_training(
+        warmup: Duration,
+        work: Duration,
+        rest: Duration,
+        rounds: u32,
+        cooldown: Duration,
+    ) -> Self {
+        let mut sequence = Self::new();
+        if !warmup.is_zero() {
+            sequence = sequence.segment(Segment::new("Warmup", warmup, 0xffc107));
+        }
+        let mut block = vec![Segment::new("Work", work, 0xdc3545)];
+        if !rest.is_zero() {
+            block.push(Segment::new("Rest", rest, 0x28a745));
+        }
+        sequence = sequence.repeat(rounds, block);
+        if !cooldown.is_zero() {
+            sequence = sequence.segment(Segment::new("Cooldown", cooldown, 0x17a2b8));
+        }
+        sequence
+    }
+
+    /// Appends a single segment
+    pub fn segment(mut self, segment: Segment) -> Self {
+        self.schedule.push(ScheduledSegment { segment, round: None });
+        self
+    }
+
+    /// Appends `block` repeated `rounds` times
+    pub fn repeat(mut self, rounds: u32, block: Vec<Segment>) -> Self {
+        for round in 1..=rounds {
+            self.schedule.extend(block.iter().cloned().map(|segment| ScheduledSegment {
+                segment,
+                round: Some((round, rounds)),
+            }));
+        }
+        self
+    }
+
+    /// Gets every segment in running order
+    pub fn schedule(&self) -> &[ScheduledSegment] {
+        &self.schedule
+    }
+
+    /// Gets the combined length of all segments
+    pub fn total_duration(&self) -> Duration {
+        self.schedule.iter().map(|scheduled| scheduled.segment.duration).sum()
+    }
+
+    /// Validates that the program has segments and each one is a valid timer duration
+    pub fn validate(&self) -> Result<(), TimerError> {
+        if self.schedule.is_empty() {
+            return Err(TimerError::EmptySequence);
+        }
+
+        for scheduled in &self.schedule {
+            validate_duration(scheduled.segment.duration).map_err(|error| TimerError::InvalidSegment {
+                label: scheduled.segment.label.clone(),
+                source: Box::new(error),
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Transition reported by [`SequenceRunner::tick`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// The segment at `index` in the schedule has started
+    SegmentStarted(usize),
+    /// The last segment completed
+    Finished,
+}
+
+/// Runs a [`Sequence`] segment by segment on a TimerLogic
+///
+/// Like [`Pomodoro`](crate::timer::Pomodoro), the runner doesn't own the
+/// timer: call [`tick`](Self::tick) on the runner instead of on the timer.
+#[derive(Debug, Clone)]
+pub struct SequenceRunner {
+    sequence: Sequence,
+    current: usize,
+    finished: bool,
+}
+
+impl SequenceRunner {
+    /// Creates a runner positioned at the first segment
+    pub fn new(sequence: Sequence) -> Result<Self, TimerError> {
+        sequence.validate()?;
+        Ok(Self {
+            sequence,
+            current: 0,
+            finished: false,
+        })
+    }
+
+    /// Gets the program being run
+    pub fn sequence(&self) -> &Sequence {
+        &self.sequence
+    }
+
+    /// Gets the index of the current segment in the schedule
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    /// Gets the current segment
+    pub fn current(&self) -> &ScheduledSegment {
+        &self.sequence.schedule[self.current]
+    }
+
+    /// Checks if every segment has completed
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Restarts the program and loads the first segment into `timer`
+    ///
+    /// The timer is left stopped; start it to begin the program.
+    pub fn begin(&mut self, timer: &mut TimerLogic) {
+        self.current = 0;
+        self.finished = false;
+        self.load_current(timer);
+    }
+
+    /// Ticks `timer` and moves to the next segment when the current one completes
+    ///
+    /// The next segment starts running immediately, including the time the
+    /// completed one ran past its end, so segments don't drift behind the
+    /// clock. If several boundaries passed since the last call, every segment
+    /// in between completes and only the last transition is returned.
+    pub fn tick(&mut self, timer: &mut TimerLogic) -> Option<SequenceEvent> {
+        if self.finished || !timer.tick() {
+            return None;
+        }
+
+        loop {
+            if self.current + 1 == self.sequence.schedule.len() {
+                self.finished = true;
+                return Some(SequenceEvent::Finished);
+            }
+
+            let overrun = timer.overrun();
+            self.current += 1;
+            self.load_current(timer);
+            timer.start_timer_after(overrun);
+            if !timer.tick() {
+                return Some(SequenceEvent::SegmentStarted(self.current));
+            }
+        }
+    }
+
+    fn load_current(&self, timer: &mut TimerLogic) {
+        timer
+            .set_duration(self.current().segment.duration)
+            .expect("segment durations are validated on construction");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::MockClock;
+
+    fn secs(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_schedule_flattens_rounds() {
+        let sequence = Sequence::interval_training(secs(300), secs(20), secs(10), 8, secs(180));
+        let schedule = sequence.schedule();
+
+        assert_eq!(schedule.len(), 1 + 8 * 2 + 1);
+        assert_eq!(schedule[0].announcement(), "Warmup");
+        assert_eq!(schedule[1].announcement(), "Work (round 1 of 8)");
+        assert_eq!(schedule[2].announcement(), "Rest (round 1 of 8)");
+        assert_eq!(schedule[16].announcement(), "Rest (round 8 of 8)");
+        assert_eq!(schedule[17].announcement(), "Cooldown");
+        assert_eq!(sequence.total_duration(), secs(300 + 8 * 30 + 180));
+    }
+
+    #[test]
+    fn test_invalid_sequences() {
+        assert_eq!(SequenceRunner::new(Sequence::new()).unwrap_err(), TimerError::EmptySequence);
+
+        let sequence = Sequence::new().segment(Segment::new("Hold", Duration::ZERO, 0));
+        let error = SequenceRunner::new(sequence).unwrap_err();
+        assert!(matches!(error, TimerError::InvalidSegment { ref label, .. } if label == "Hold"));
+        assert!(error.to_string().contains("Segment \"Hold\" is invalid"));
+    }
+
+    #[test]
+    fn test_segment_boundaries() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        let sequence = Sequence::interval_training(secs(5), secs(20), secs(10), 2, Duration::ZERO);
+        let mut runner = SequenceRunner::new(sequence).unwrap();
+
+        runner.begin(&mut timer);
+        assert_eq!(runner.current().segment.label, "Warmup");
+        assert_eq!(timer.get_remaining_time_string(), "00:00:05");
+        timer.start_timer();
+
+        // Just before the boundary nothing changes
+        clock.advance(Duration::from_millis(4_999));
+        assert_eq!(runner.tick(&mut timer), None);
+        assert_eq!(runner.current_index(), 0);
+
+        // Crossing the boundary loads and starts the next segment
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::SegmentStarted(1)));
+        assert_eq!(runner.current().announcement(), "Work (round 1 of 2)");
+        assert_eq!(timer.get_remaining_time_string(), "00:00:20");
+        assert!(timer.is_running());
+
+        clock.advance(secs(20));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::SegmentStarted(2)));
+        assert_eq!(runner.current().announcement(), "Rest (round 1 of 2)");
+        clock.advance(secs(10));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::SegmentStarted(3)));
+        clock.advance(secs(20));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::SegmentStarted(4)));
+        assert_eq!(runner.current().announcement(), "Rest (round 2 of 2)");
+
+        // The last segment finishes the program and leaves the timer completed
+        clock.advance(secs(10));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::Finished));
+        assert!(runner.is_finished());
+        assert!(timer.is_completed());
+        assert_eq!(runner.tick(&mut timer), None);
+    }
+
+    #[test]
+    fn test_late_tick_carries_over_boundaries() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        let sequence = Sequence::interval_training(secs(5), secs(20), secs(10), 2, Duration::ZERO);
+        let mut runner = SequenceRunner::new(sequence).unwrap();
+
+        runner.begin(&mut timer);
+        timer.start_timer();
+
+        // A stall past the warmup and first work segment lands 3s into the rest
+        clock.advance(secs(28));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::SegmentStarted(2)));
+        assert_eq!(runner.current().announcement(), "Rest (round 1 of 2)");
+        assert_eq!(timer.get_remaining_time_string(), "00:00:07");
+
+        // A late tick doesn't push the following boundaries back
+        clock.advance(Duration::from_millis(7_400));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::SegmentStarted(3)));
+        clock.advance(Duration::from_millis(19_600));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::SegmentStarted(4)));
+
+        clock.advance(secs(100));
+        assert_eq!(runner.tick(&mut timer), Some(SequenceEvent::Finished));
+        assert!(timer.is_completed());
+    }
+
+    #[test]
+    fn test_pausing_holds_the_segment() {
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_clock(clock.clone());
+        let sequence = Sequence::interval_training(Duration::ZERO, secs(20), secs(10), 1, Duration::ZERO);
+        let mut runner = SequenceRunner::new(sequence).unwrap();
+
+        runner.begin(&mut timer);
+        timer.start_timer();
+        clock.advance(secs(15));
+        runner.tick(&mut timer);
+        timer.pause_timer();
+
+        clock.advance(secs(60));
+        assert_eq!(runner.tick(&mut timer), None);
+        assert_eq!(runner.current().segment.label, "Work");
+        assert_eq!(timer.get_remaining_time_string(), "00:00:05");
+    }
+}
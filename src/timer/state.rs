@@ -1,49 +1,361 @@
-#[derive(Debug, Clone)]
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+
+/// Whether a timer counts down from a set duration or up from zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TimerMode {
+    /// Counts down from the configured duration and completes at zero
+    #[default]
+    Countdown,
+    /// Counts up from zero until reset
+    Stopwatch,
+}
+
+impl TimerMode {
+    /// Gets the name the mode is stored under, e.g. "countdown"
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            TimerMode::Countdown => "countdown",
+            TimerMode::Stopwatch => "stopwatch",
+        }
+    }
+
+    /// Gets the mode stored under `key` by [`key`](Self::key)
+    pub(crate) fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "countdown" => Some(TimerMode::Countdown),
+            "stopwatch" => Some(TimerMode::Stopwatch),
+            _ => None,
+        }
+    }
+}
+
+/// How finely the remaining time is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DisplayPrecision {
+    /// Whole seconds, e.g. `00:01:23`
+    #[default]
+    Seconds,
+    /// Tenths of a second, e.g. `00:01:23.4`
+    Tenths,
+    /// Hundredths of a second, e.g. `00:01:23.45`
+    Hundredths,
+}
+
+impl DisplayPrecision {
+    /// Length of the smallest displayed unit
+    pub fn unit(self) -> Duration {
+        match self {
+            DisplayPrecision::Seconds => Duration::from_secs(1),
+            DisplayPrecision::Tenths => Duration::from_millis(100),
+            DisplayPrecision::Hundredths => Duration::from_millis(10),
+        }
+    }
+
+    /// How often a running display should be refreshed to show every unit
+    pub fn refresh_interval(self) -> Duration {
+        match self {
+            DisplayPrecision::Seconds => Duration::from_millis(100),
+            DisplayPrecision::Tenths => Duration::from_millis(50),
+            DisplayPrecision::Hundredths => Duration::from_millis(10),
+        }
+    }
+
+    /// Number of displayed units in `duration`, rounded up so a countdown
+    /// only shows zero once it has fully elapsed
+    pub fn units(self, duration: Duration) -> u128 {
+        duration.as_nanos().div_ceil(self.unit().as_nanos())
+    }
+
+    /// Number of whole displayed units in `duration`, rounded down so a
+    /// stopwatch only shows a unit once it has fully elapsed
+    pub fn elapsed_units(self, duration: Duration) -> u128 {
+        duration.as_nanos() / self.unit().as_nanos()
+    }
+
+    /// Formats a remaining `duration` as `HH:MM:SS` (or `D:HH:MM:SS` from a
+    /// day upwards) followed by any fractional digits, rounding partial units up
+    pub fn format(self, duration: Duration) -> String {
+        self.format_units(self.units(duration))
+    }
+
+    /// Formats an elapsed `duration` like [`format`](Self::format), rounding
+    /// partial units down
+    pub fn format_elapsed(self, duration: Duration) -> String {
+        self.format_units(self.elapsed_units(duration))
+    }
+
+    fn format_units(self, units: u128) -> String {
+        let (total_seconds, fraction) = match self {
+            DisplayPrecision::Seconds => (units, String::new()),
+            DisplayPrecision::Tenths => (units / 10, format!(".{}", units % 10)),
+            DisplayPrecision::Hundredths => (units / 100, format!(".{:02}", units % 100)),
+        };
+        let days = total_seconds / 86_400;
+        let hours = (total_seconds % 86_400) / 3600;
+        let minutes = (total_seconds % 3600) / 60;
+        let seconds = total_seconds % 60;
+        if days > 0 {
+            format!("{}:{:02}:{:02}:{:02}{}", days, hours, minutes, seconds, fraction)
+        } else {
+            format!("{:02}:{:02}:{:02}{}", hours, minutes, seconds, fraction)
+        }
+    }
+}
+
+/// A lap recorded while the timer was running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lap {
+    /// 1-based position of the lap in the run
+    pub number: usize,
+    /// Running time of the run when the lap was recorded
+    pub elapsed: Duration,
+    /// Running time since the previous lap, or since the start for the first lap
+    pub split: Duration,
+}
+
+/// Snapshot of a timer's configured duration and countdown progress
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimerState {
+    /// Configured days (0-99)
+    pub days: u32,
+    /// Configured hours (0-23)
     pub hours: u32,
+    /// Configured minutes (0-59)
     pub minutes: u32,
+    /// Configured seconds (0-59)
     pub seconds: u32,
-    pub remaining_seconds: u32,
+    /// Whether this is a countdown or a stopwatch
+    pub mode: TimerMode,
+    /// Wall-clock time a countdown to an absolute time ends at; `None` for a
+    /// countdown of a fixed duration
+    pub target: Option<DateTime<FixedOffset>>,
+    /// Time left until the countdown completes; always zero for a stopwatch
+    pub remaining: Duration,
+    /// Running time so far, excluding time spent paused
+    pub elapsed: Duration,
+    /// Time counted past zero by a countdown in overtime mode
+    pub overtime: Duration,
+    /// Laps recorded during the current run, oldest first
+    pub laps: Vec<Lap>,
+    /// Wall-clock time the current run would have started at had it never
+    /// been paused, so `elapsed` can be recovered from it; `None` unless running
+    pub started_at: Option<DateTime<FixedOffset>>,
+    /// Whether the countdown is currently running
     pub is_running: bool,
+    /// Whether the countdown has reached zero
     pub is_completed: bool,
-}
-
-impl Default for TimerState {
-    fn default() -> Self {
-        Self {
-            hours: 0,
-            minutes: 0,
-            seconds: 0,
-            remaining_seconds: 0,
-            is_running: false,
-            is_completed: false,
-        }
-    }
+    /// Whether a countdown keeps counting past zero instead of stopping
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub overtime_enabled: bool,
+    /// What the timer is being used for, e.g. "Write report"
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: Option<String>,
 }
 
 impl TimerState {
+    /// Creates a stopped state counting down from the given duration
     pub fn new(hours: u32, minutes: u32, seconds: u32) -> Self {
-        let total_seconds = hours * 3600 + minutes * 60 + seconds;
-        Self {
+        Self::with_days(0, hours, minutes, seconds)
+    }
+
+    /// Creates a stopped state counting down from the given number of days
+    /// plus hours, minutes and seconds
+    pub fn with_days(days: u32, hours: u32, minutes: u32, seconds: u32) -> Self {
+        let mut state = Self {
+            days,
             hours,
             minutes,
             seconds,
-            remaining_seconds: total_seconds,
-            is_running: false,
-            is_completed: false,
+            ..Self::default()
+        };
+        state.remaining = state.duration();
+        state
+    }
+
+    /// Creates a stopped stopwatch at zero
+    pub fn stopwatch() -> Self {
+        Self {
+            mode: TimerMode::Stopwatch,
+            ..Self::default()
         }
     }
 
+    /// Restores the configured duration and clears the running/completed flags
     pub fn reset(&mut self) {
-        self.remaining_seconds = self.hours * 3600 + self.minutes * 60 + self.seconds;
+        self.remaining = self.duration();
+        self.elapsed = Duration::ZERO;
+        self.overtime = Duration::ZERO;
+        self.laps.clear();
+        self.started_at = None;
         self.is_running = false;
         self.is_completed = false;
     }
 
+    /// Gets the configured duration of the countdown
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(
+            u64::from(self.days) * 86_400
+                + u64::from(self.hours) * 3600
+                + u64::from(self.minutes) * 60
+                + u64::from(self.seconds),
+        )
+    }
+
+    /// Checks if a countdown has run past zero
+    pub fn is_overtime(&self) -> bool {
+        !self.overtime.is_zero()
+    }
+
+    /// Gets the remaining time in whole seconds, rounded up
+    pub fn remaining_seconds(&self) -> u32 {
+        DisplayPrecision::Seconds.units(self.remaining) as u32
+    }
+
+    /// Formats the remaining time as `HH:MM:SS`, or `D:HH:MM:SS` from a day upwards
     pub fn format_remaining_time(&self) -> String {
-        let hours = self.remaining_seconds / 3600;
-        let minutes = (self.remaining_seconds % 3600) / 60;
-        let seconds = self.remaining_seconds % 60;
-        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+        self.format_remaining_time_with(DisplayPrecision::Seconds)
+    }
+
+    /// Formats the remaining time at the given precision, e.g. `00:01:23.45`
+    ///
+    /// Once a countdown is a full unit into overtime, the time past zero is
+    /// shown as a negative value, e.g. `-00:02:13`.
+    pub fn format_remaining_time_with(&self, precision: DisplayPrecision) -> String {
+        if precision.elapsed_units(self.overtime) > 0 {
+            format!("-{}", precision.format_elapsed(self.overtime))
+        } else {
+            precision.format(self.remaining)
+        }
+    }
+
+    /// Gets the lap with the shortest split, if at least two laps were recorded
+    pub fn fastest_lap(&self) -> Option<&Lap> {
+        if self.laps.len() < 2 {
+            return None;
+        }
+        self.laps.iter().min_by_key(|lap| lap.split)
+    }
+
+    /// Gets the lap with the longest split, if at least two laps were recorded
+    pub fn slowest_lap(&self) -> Option<&Lap> {
+        if self.laps.len() < 2 {
+            return None;
+        }
+        self.laps.iter().max_by_key(|lap| lap.split)
+    }
+
+    /// Formats the time to show for this mode: the remaining time of a
+    /// countdown or the elapsed time of a stopwatch
+    pub fn format_display_time_with(&self, precision: DisplayPrecision) -> String {
+        match self.mode {
+            TimerMode::Countdown => self.format_remaining_time_with(precision),
+            TimerMode::Stopwatch => precision.format_elapsed(self.elapsed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_precisions() {
+        let remaining = Duration::from_millis(83_450);
+        assert_eq!(DisplayPrecision::Seconds.format(remaining), "00:01:24");
+        assert_eq!(DisplayPrecision::Tenths.format(remaining), "00:01:23.5");
+        assert_eq!(DisplayPrecision::Hundredths.format(remaining), "00:01:23.45");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_format_rounds_up_partial_units() {
+        let remaining = Duration::from_micros(1);
+        assert_eq!(DisplayPrecision::Seconds.format(remaining), "00:00:01");
+        assert_eq!(DisplayPrecision::Tenths.format(remaining), "00:00:00.1");
+        assert_eq!(DisplayPrecision::Hundredths.format(remaining), "00:00:00.01");
+        assert_eq!(DisplayPrecision::Hundredths.format(Duration::ZERO), "00:00:00.00");
+    }
+
+    #[test]
+    fn test_remaining_seconds_from_duration() {
+        let mut state = TimerState::new(1, 2, 3);
+        assert_eq!(state.remaining_seconds(), 3723);
+        assert_eq!(state.format_remaining_time(), "01:02:03");
+
+        state.remaining = Duration::from_millis(1001);
+        assert_eq!(state.remaining_seconds(), 2);
+
+        state.reset();
+        assert_eq!(state.remaining, Duration::from_secs(3723));
+    }
+
+    #[test]
+    fn test_day_scale_formatting() {
+        let state = TimerState::with_days(3, 4, 5, 6);
+        assert_eq!(state.duration(), Duration::from_secs(3 * 86_400 + 4 * 3600 + 5 * 60 + 6));
+        assert_eq!(state.format_remaining_time(), "3:04:05:06");
+        assert_eq!(state.format_remaining_time_with(DisplayPrecision::Tenths), "3:04:05:06.0");
+        assert_eq!(DisplayPrecision::Seconds.format(Duration::from_secs(86_399)), "23:59:59");
+        assert_eq!(DisplayPrecision::Seconds.format_elapsed(Duration::from_secs(86_400)), "1:00:00:00");
+    }
+
+    #[test]
+    fn test_stopwatch_display_rounds_down() {
+        let mut state = TimerState::stopwatch();
+        assert_eq!(state.mode, TimerMode::Stopwatch);
+        assert_eq!(state.format_display_time_with(DisplayPrecision::Seconds), "00:00:00");
+
+        state.elapsed = Duration::from_millis(83_459);
+        assert_eq!(state.format_display_time_with(DisplayPrecision::Seconds), "00:01:23");
+        assert_eq!(state.format_display_time_with(DisplayPrecision::Hundredths), "00:01:23.45");
+
+        state.reset();
+        assert_eq!(state.elapsed, Duration::ZERO);
+        assert_eq!(state.mode, TimerMode::Stopwatch);
+    }
+
+    #[test]
+    fn test_fastest_and_slowest_laps() {
+        let lap = |number, split_secs| Lap {
+            number,
+            elapsed: Duration::ZERO,
+            split: Duration::from_secs(split_secs),
+        };
+        let mut state = TimerState::stopwatch();
+        state.laps.push(lap(1, 40));
+        assert_eq!(state.fastest_lap(), None);
+        assert_eq!(state.slowest_lap(), None);
+
+        state.laps.push(lap(2, 35));
+        state.laps.push(lap(3, 52));
+        assert_eq!(state.fastest_lap().map(|lap| lap.number), Some(2));
+        assert_eq!(state.slowest_lap().map(|lap| lap.number), Some(3));
+
+        state.reset();
+        assert!(state.laps.is_empty());
+    }
+
+    #[test]
+    fn test_overtime_formats_as_negative() {
+        let mut state = TimerState::new(0, 5, 0);
+        state.remaining = Duration::ZERO;
+        state.overtime = Duration::from_millis(999);
+        assert!(state.is_overtime());
+        assert_eq!(state.format_remaining_time(), "00:00:00");
+        assert_eq!(state.format_remaining_time_with(DisplayPrecision::Tenths), "-00:00:00.9");
+
+        state.overtime = Duration::from_millis(133_400);
+        assert_eq!(state.format_remaining_time(), "-00:02:13");
+
+        state.reset();
+        assert!(!state.is_overtime());
+        assert_eq!(state.format_remaining_time(), "00:05:00");
+    }
+}
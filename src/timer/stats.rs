@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDate, TimeDelta};
+
+use crate::timer::{SessionOutcome, SessionRecord, TimerMode};
+
+/// Time spent in the sessions started during one day or week
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodTotal {
+    /// First day of the period
+    pub start: NaiveDate,
+    /// Running time of the sessions started in the period
+    pub total: Duration,
+    /// Number of sessions started in the period
+    pub sessions: usize,
+}
+
+/// Runs of consecutive days with at least one completed session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Streaks {
+    /// Length in days of the streak ending today, or yesterday if nothing
+    /// has been completed yet today
+    pub current: u32,
+    /// Length in days of the longest streak
+    pub longest: u32,
+}
+
+/// Gets the day a session counts towards: the date it started on, in the
+/// time zone it was recorded in
+fn session_day(record: &SessionRecord) -> NaiveDate {
+    record.started_at.date_naive()
+}
+
+/// Totals the sessions started on each day from `first` to `last`, inclusive
+///
+/// Days without sessions are included with a zero total, so the result
+/// has one entry per day and can be charted directly.
+pub fn daily_totals(records: &[SessionRecord], first: NaiveDate, last: NaiveDate) -> Vec<PeriodTotal> {
+    let mut totals: Vec<PeriodTotal> = first
+        .iter_days()
+        .take_while(|day| *day <= last)
+        .map(|start| PeriodTotal { start, total: Duration::ZERO, sessions: 0 })
+        .collect();
+    for record in records {
+        let Ok(index) = usize::try_from((session_day(record) - first).num_days()) else {
+            continue;
+        };
+        if let Some(total) = totals.get_mut(index) {
+            total.total += record.elapsed;
+            total.sessions += 1;
+        }
+    }
+    totals
+}
+
+/// Totals the sessions started in each Monday-to-Sunday week that overlaps
+/// `first` to `last`
+///
+/// Weeks are keyed by their Monday and only count sessions started between
+/// `first` and `last`.
+pub fn weekly_totals(records: &[SessionRecord], first: NaiveDate, last: NaiveDate) -> Vec<PeriodTotal> {
+    let monday = |day: NaiveDate| day - TimeDelta::days(i64::from(day.weekday().num_days_from_monday()));
+    let mut weeks: Vec<PeriodTotal> = Vec::new();
+    for day in daily_totals(records, first, last) {
+        match weeks.last_mut() {
+            Some(week) if week.start == monday(day.start) => {
+                week.total += day.total;
+                week.sessions += day.sessions;
+            }
+            _ => weeks.push(PeriodTotal { start: monday(day.start), ..day }),
+        }
+    }
+    weeks
+}
+
+/// Gets the share of countdown runs that completed, from 0.0 to 1.0
+///
+/// Stopwatch runs never complete, so they are left out. Returns `None`
+/// when there are no countdown runs.
+pub fn completion_rate(records: &[SessionRecord]) -> Option<f64> {
+    let countdowns = records.iter().filter(|record| record.mode == TimerMode::Countdown);
+    let (completed, total) = countdowns.fold((0usize, 0usize), |(completed, total), record| {
+        (completed + usize::from(record.outcome == SessionOutcome::Completed), total + 1)
+    });
+    (total > 0).then(|| completed as f64 / total as f64)
+}
+
+/// Gets the mean running time of the sessions, or `None` if there are none
+pub fn average_length(records: &[SessionRecord]) -> Option<Duration> {
+    let count = u32::try_from(records.len()).ok().filter(|count| *count > 0)?;
+    let total: Duration = records.iter().map(|record| record.elapsed).sum();
+    Some(total / count)
+}
+
+/// Finds the current and longest runs of consecutive days with at least
+/// one completed session, as of `today`
+pub fn streaks(records: &[SessionRecord], today: NaiveDate) -> Streaks {
+    let mut days: Vec<NaiveDate> = records
+        .iter()
+        .filter(|record| record.outcome == SessionOutcome::Completed)
+        .map(session_day)
+        .filter(|day| *day <= today)
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let mut streaks = Streaks::default();
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in &days {
+        run = match previous {
+            Some(previous) if previous.succ_opt() == Some(*day) => run + 1,
+            _ => 1,
+        };
+        streaks.longest = streaks.longest.max(run);
+        previous = Some(*day);
+    }
+    if let Some(last) = previous {
+        let yesterday = today.pred_opt();
+        if last == today || Some(last) == yesterday {
+            streaks.current = run;
+        }
+    }
+    streaks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, FixedOffset, TimeZone};
+
+    /// A session started at `hour` o'clock on 2024-01-`day` (a Monday for day 1)
+    fn session(day: u32, hour: u32, minutes: u64, outcome: SessionOutcome) -> SessionRecord {
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let started_at: DateTime<FixedOffset> = offset.with_ymd_and_hms(2024, 1, day, hour, 0, 0).unwrap();
+        SessionRecord {
+            mode: TimerMode::Countdown,
+            configured: Duration::from_secs(25 * 60),
+            elapsed: Duration::from_secs(minutes * 60),
+            started_at,
+            ended_at: started_at + TimeDelta::minutes(minutes as i64),
+            outcome,
+            label: None,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn dataset() -> Vec<SessionRecord> {
+        vec![
+            session(1, 9, 25, SessionOutcome::Completed),
+            session(1, 14, 10, SessionOutcome::Reset),
+            session(2, 9, 25, SessionOutcome::Completed),
+            session(3, 23, 25, SessionOutcome::Completed),
+            session(5, 9, 5, SessionOutcome::Abandoned),
+            session(8, 9, 25, SessionOutcome::Completed),
+            session(9, 9, 25, SessionOutcome::Completed),
+        ]
+    }
+
+    #[test]
+    fn test_daily_totals_include_empty_days() {
+        let totals = daily_totals(&dataset(), date(1), date(5));
+        let minutes: Vec<_> = totals.iter().map(|day| (day.total.as_secs() / 60, day.sessions)).collect();
+        assert_eq!(minutes, [(35, 2), (25, 1), (25, 1), (0, 0), (5, 1)]);
+        assert_eq!(totals[3].start, date(4));
+    }
+
+    #[test]
+    fn test_sessions_count_towards_their_local_start_day() {
+        // 23:00 at UTC+2 on the 3rd is still the 3rd, although it is 21:00 UTC
+        let totals = daily_totals(&[session(3, 23, 25, SessionOutcome::Completed)], date(3), date(4));
+        assert_eq!(totals[0].sessions, 1);
+        assert_eq!(totals[1].sessions, 0);
+    }
+
+    #[test]
+    fn test_weekly_totals_start_on_monday() {
+        let totals = weekly_totals(&dataset(), date(3), date(14));
+        let weeks: Vec<_> = totals.iter().map(|week| (week.start, week.total.as_secs() / 60)).collect();
+        assert_eq!(weeks, [(date(1), 30), (date(8), 50)]);
+    }
+
+    #[test]
+    fn test_completion_rate() {
+        assert_eq!(completion_rate(&dataset()), Some(5.0 / 7.0));
+        assert_eq!(completion_rate(&[]), None);
+
+        let stopwatch = SessionRecord { mode: TimerMode::Stopwatch, ..session(1, 9, 5, SessionOutcome::Reset) };
+        assert_eq!(completion_rate(&[stopwatch]), None);
+    }
+
+    #[test]
+    fn test_average_length() {
+        assert_eq!(average_length(&dataset()), Some(Duration::from_secs(140 * 60 / 7)));
+        assert_eq!(average_length(&[]), None);
+    }
+
+    #[test]
+    fn test_streaks() {
+        // Completed on the 1st-3rd, then the 8th-9th
+        assert_eq!(streaks(&dataset(), date(9)), Streaks { current: 2, longest: 3 });
+        assert_eq!(streaks(&dataset(), date(10)), Streaks { current: 2, longest: 3 });
+        assert_eq!(streaks(&dataset(), date(11)), Streaks { current: 0, longest: 3 });
+        // Sessions after `today` are ignored
+        assert_eq!(streaks(&dataset(), date(4)), Streaks { current: 3, longest: 3 });
+        assert_eq!(streaks(&[], date(4)), Streaks::default());
+    }
+}
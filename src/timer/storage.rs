@@ -0,0 +1,419 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::timer::{data_dir, Preset, SessionOutcome, SessionRecord, StorageError, TimerError, TimerMode};
+
+/// Schema changes, applied in order to bring a database up to date
+///
+/// The number of migrations applied is kept in SQLite's `user_version`, so
+/// existing entries must never change; add a new one instead.
+const MIGRATIONS: &[&str] = &[
+    // 1: sessions, presets and settings
+    "CREATE TABLE sessions (
+        id INTEGER PRIMARY KEY,
+        started_at TEXT NOT NULL,
+        started_at_ms INTEGER NOT NULL,
+        ended_at TEXT NOT NULL,
+        outcome TEXT NOT NULL,
+        mode TEXT NOT NULL,
+        configured_ns INTEGER NOT NULL,
+        elapsed_ns INTEGER NOT NULL,
+        label TEXT
+    );
+    CREATE INDEX sessions_by_start ON sessions (started_at_ms);
+    CREATE INDEX sessions_by_label ON sessions (label);
+    CREATE TABLE presets (
+        position INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE,
+        duration_ns INTEGER NOT NULL
+    );
+    CREATE TABLE settings (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );",
+];
+
+/// Schema version of a database opened by this version of the crate
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Filter for [`Database::sessions`]
+///
+/// The default query matches every session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionQuery {
+    /// Only sessions started at or after this time
+    pub from: Option<DateTime<FixedOffset>>,
+    /// Only sessions started before this time
+    pub to: Option<DateTime<FixedOffset>>,
+    /// Only sessions with this label, ignoring ASCII case
+    pub label: Option<String>,
+}
+
+impl SessionQuery {
+    /// Creates a query that matches every session
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the query to sessions started in `from..to`
+    pub fn between(mut self, from: DateTime<FixedOffset>, to: DateTime<FixedOffset>) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    /// Restricts the query to sessions labeled `label`
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// Local SQLite database holding the session history, presets and settings
+///
+/// Opening a database applies any pending [migrations](SCHEMA_VERSION), so
+/// a file written by an older version is upgraded in place.
+pub struct Database {
+    connection: Connection,
+}
+
+impl Database {
+    /// Opens or creates the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, TimerError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|error| TimerError::Storage(StorageError::new(error)))?;
+        }
+        Self::with_connection(Connection::open(path).map_err(storage_error)?)
+    }
+
+    /// Opens a fresh database that only lives in memory, e.g. for tests
+    pub fn open_in_memory() -> Result<Self, TimerError> {
+        Self::with_connection(Connection::open_in_memory().map_err(storage_error)?)
+    }
+
+    /// Gets the path of the file `timer.sqlite3` in the application's [`data_dir`]
+    pub fn default_path() -> Option<PathBuf> {
+        Some(data_dir()?.join("timer.sqlite3"))
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, TimerError> {
+        let mut database = Self { connection };
+        database.migrate()?;
+        Ok(database)
+    }
+
+    /// Gets the schema version the database is at
+    pub fn schema_version(&self) -> Result<u32, TimerError> {
+        self.connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .map_err(storage_error)
+    }
+
+    /// Applies the migrations the database hasn't seen yet, in one transaction
+    fn migrate(&mut self) -> Result<(), TimerError> {
+        let version = self.schema_version()?;
+        if version > SCHEMA_VERSION {
+            return Err(TimerError::UnsupportedSchemaVersion(version));
+        }
+
+        let transaction = self.connection.transaction().map_err(storage_error)?;
+        for migration in &MIGRATIONS[version as usize..] {
+            transaction.execute_batch(migration).map_err(storage_error)?;
+        }
+        transaction
+            .pragma_update(None, "user_version", SCHEMA_VERSION)
+            .map_err(storage_error)?;
+        transaction.commit().map_err(storage_error)
+    }
+
+    /// Adds a finished run to the session history
+    pub fn add_session(&self, record: &SessionRecord) -> Result<(), TimerError> {
+        self.connection
+            .execute(
+                "INSERT INTO sessions
+                    (started_at, started_at_ms, ended_at, outcome, mode, configured_ns, elapsed_ns, label)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    record.started_at.to_rfc3339(),
+                    record.started_at.timestamp_millis(),
+                    record.ended_at.to_rfc3339(),
+                    record.outcome.key(),
+                    record.mode.key(),
+                    nanos_to_sql(record.configured),
+                    nanos_to_sql(record.elapsed),
+                    record.label,
+                ],
+            )
+            .map_err(storage_error)?;
+        Ok(())
+    }
+
+    /// Gets the sessions matching `query`, oldest first
+    pub fn sessions(&self, query: &SessionQuery) -> Result<Vec<SessionRecord>, TimerError> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT started_at, ended_at, outcome, mode, configured_ns, elapsed_ns, label
+                 FROM sessions
+                 WHERE (?1 IS NULL OR started_at_ms >= ?1)
+                   AND (?2 IS NULL OR started_at_ms < ?2)
+                   AND (?3 IS NULL OR label = ?3 COLLATE NOCASE)
+                 ORDER BY started_at_ms, id",
+            )
+            .map_err(storage_error)?;
+        let rows = statement
+            .query_map(
+                params![
+                    query.from.map(|from| from.timestamp_millis()),
+                    query.to.map(|to| to.timestamp_millis()),
+                    query.label,
+                ],
+                session_from_row,
+            )
+            .map_err(storage_error)?;
+        rows.map(|row| row.map_err(storage_error)).collect()
+    }
+
+    /// Gets every distinct session label, sorted alphabetically
+    pub fn labels(&self) -> Result<Vec<String>, TimerError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT DISTINCT label FROM sessions WHERE label IS NOT NULL ORDER BY label")
+            .map_err(storage_error)?;
+        let rows = statement.query_map([], |row| row.get(0)).map_err(storage_error)?;
+        rows.map(|row| row.map_err(storage_error)).collect()
+    }
+
+    /// Gets the saved presets in their saved order
+    pub fn presets(&self) -> Result<Vec<Preset>, TimerError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT name, duration_ns FROM presets ORDER BY position")
+            .map_err(storage_error)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok(Preset {
+                    name: row.get(0)?,
+                    duration: nanos_from_sql(row.get(1)?).map_err(|error| invalid_value(1, error.to_string()))?,
+                })
+            })
+            .map_err(storage_error)?;
+        rows.map(|row| row.map_err(storage_error)).collect()
+    }
+
+    /// Replaces the saved presets with `presets`, keeping their order
+    pub fn save_presets(&mut self, presets: &[Preset]) -> Result<(), TimerError> {
+        let transaction = self.connection.transaction().map_err(storage_error)?;
+        transaction.execute("DELETE FROM presets", []).map_err(storage_error)?;
+        for (position, preset) in presets.iter().enumerate() {
+            transaction
+                .execute(
+                    "INSERT INTO presets (position, name, duration_ns) VALUES (?1, ?2, ?3)",
+                    params![position as i64, preset.name, nanos_to_sql(preset.duration)],
+                )
+                .map_err(storage_error)?;
+        }
+        transaction.commit().map_err(storage_error)
+    }
+
+    /// Gets the value of a setting, or `None` if it was never set
+    pub fn setting(&self, key: &str) -> Result<Option<String>, TimerError> {
+        self.connection
+            .query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| row.get(0))
+            .optional()
+            .map_err(storage_error)
+    }
+
+    /// Sets the value of a setting, replacing any previous value
+    pub fn set_setting(&self, key: &str, value: &str) -> Result<(), TimerError> {
+        self.connection
+            .execute(
+                "INSERT INTO settings (key, value) VALUES (?1, ?2)
+                 ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+                [key, value],
+            )
+            .map_err(storage_error)?;
+        Ok(())
+    }
+
+    /// Removes a setting, so it reads as never set
+    pub fn remove_setting(&self, key: &str) -> Result<(), TimerError> {
+        self.connection
+            .execute("DELETE FROM settings WHERE key = ?1", [key])
+            .map_err(storage_error)?;
+        Ok(())
+    }
+}
+
+fn storage_error(error: rusqlite::Error) -> TimerError {
+    TimerError::Storage(StorageError::new(error))
+}
+
+/// SQLite integers are signed 64-bit, which holds durations of ~292 years
+fn nanos_to_sql(duration: Duration) -> i64 {
+    i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX)
+}
+
+fn nanos_from_sql(nanos: i64) -> Result<Duration, TimerError> {
+    u64::try_from(nanos)
+        .map(Duration::from_nanos)
+        .map_err(|_| TimerError::Storage(StorageError::new(format!("negative duration {} ns", nanos))))
+}
+
+/// Wraps an invalid stored value so it fails the query like any SQL error
+fn invalid_value(column: usize, reason: String) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(column, Type::Text, Box::new(TimerError::Storage(StorageError::new(reason))))
+}
+
+fn session_from_row(row: &Row<'_>) -> rusqlite::Result<SessionRecord> {
+    let time = |column: usize| {
+        let value: String = row.get(column)?;
+        DateTime::parse_from_rfc3339(&value)
+            .map_err(|_| invalid_value(column, format!("invalid session time \"{}\"", value)))
+    };
+    let nanos = |column: usize| {
+        nanos_from_sql(row.get(column)?).map_err(|error| invalid_value(column, error.to_string()))
+    };
+    let outcome: String = row.get(2)?;
+    let mode: String = row.get(3)?;
+
+    Ok(SessionRecord {
+        mode: TimerMode::from_key(&mode)
+            .ok_or_else(|| invalid_value(3, format!("invalid session mode \"{}\"", mode)))?,
+        configured: nanos(4)?,
+        elapsed: nanos(5)?,
+        started_at: time(0)?,
+        ended_at: time(1)?,
+        outcome: SessionOutcome::from_key(&outcome)
+            .ok_or_else(|| invalid_value(2, format!("invalid session outcome \"{}\"", outcome)))?,
+        label: row.get(6)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::{Clock, MockClock};
+    use chrono::TimeDelta;
+
+    fn session(started_at: DateTime<FixedOffset>, minutes: i64, label: Option<&str>) -> SessionRecord {
+        SessionRecord {
+            mode: TimerMode::Countdown,
+            configured: Duration::from_secs(minutes as u64 * 60),
+            elapsed: Duration::from_secs(minutes as u64 * 60),
+            started_at,
+            ended_at: started_at + TimeDelta::minutes(minutes),
+            outcome: SessionOutcome::Completed,
+            label: label.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_migrations_bring_new_database_up_to_date() {
+        let database = Database::open_in_memory().unwrap();
+        assert_eq!(database.schema_version().unwrap(), SCHEMA_VERSION);
+        assert!(database.sessions(&SessionQuery::all()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reopen_keeps_data_and_rejects_newer_schema() {
+        let dir = std::env::temp_dir().join(format!("rust-slint-timer-sqlite-{}", std::process::id()));
+        let path = dir.join("timer.sqlite3");
+        let clock = MockClock::new();
+        {
+            let database = Database::open(&path).unwrap();
+            database.add_session(&session(clock.wall_time(), 25, None)).unwrap();
+        }
+
+        let database = Database::open(&path).unwrap();
+        assert_eq!(database.sessions(&SessionQuery::all()).unwrap().len(), 1);
+        database
+            .connection
+            .pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+        drop(database);
+        assert_eq!(
+            Database::open(&path).err(),
+            Some(TimerError::UnsupportedSchemaVersion(SCHEMA_VERSION + 1))
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let database = Database::open_in_memory().unwrap();
+        let clock = MockClock::new();
+        let record = SessionRecord {
+            mode: TimerMode::Stopwatch,
+            configured: Duration::ZERO,
+            elapsed: Duration::from_millis(83_450),
+            outcome: SessionOutcome::Reset,
+            ..session(clock.wall_time(), 2, Some("Run"))
+        };
+        database.add_session(&record).unwrap();
+        assert_eq!(database.sessions(&SessionQuery::all()).unwrap(), [record]);
+    }
+
+    #[test]
+    fn test_query_by_date_range_and_label() {
+        let database = Database::open_in_memory().unwrap();
+        let monday = MockClock::new().wall_time();
+        for (day, label) in [(0, Some("Write report")), (1, None), (1, Some("write report")), (2, Some("Email"))] {
+            let started_at = monday + TimeDelta::days(day);
+            database.add_session(&session(started_at, 25, label)).unwrap();
+        }
+
+        let tuesday = monday + TimeDelta::days(1);
+        let on_tuesday = database
+            .sessions(&SessionQuery::all().between(tuesday, tuesday + TimeDelta::days(1)))
+            .unwrap();
+        assert_eq!(on_tuesday.len(), 2);
+        assert!(on_tuesday.iter().all(|record| record.started_at == tuesday));
+
+        let reports = database.sessions(&SessionQuery::all().with_label("WRITE REPORT")).unwrap();
+        assert_eq!(reports.len(), 2);
+        let reports_from_tuesday = database
+            .sessions(&SessionQuery::all().with_label("Write report").between(tuesday, monday + TimeDelta::days(7)))
+            .unwrap();
+        assert_eq!(reports_from_tuesday.len(), 1);
+
+        assert_eq!(database.labels().unwrap(), ["Email", "Write report", "write report"]);
+    }
+
+    #[test]
+    fn test_presets_keep_their_order() {
+        let mut database = Database::open_in_memory().unwrap();
+        let presets = [
+            Preset::new("Tea", Duration::from_secs(180)).unwrap(),
+            Preset::new("Standup", Duration::from_secs(900)).unwrap(),
+        ];
+        database.save_presets(&presets).unwrap();
+        assert_eq!(database.presets().unwrap(), presets);
+
+        let reversed = [presets[1].clone(), presets[0].clone()];
+        database.save_presets(&reversed).unwrap();
+        assert_eq!(database.presets().unwrap(), reversed);
+
+        let duplicate = [presets[0].clone(), presets[0].clone()];
+        assert!(matches!(database.save_presets(&duplicate), Err(TimerError::Storage(_))));
+        assert_eq!(database.presets().unwrap(), reversed);
+    }
+
+    #[test]
+    fn test_settings() {
+        let database = Database::open_in_memory().unwrap();
+        assert_eq!(database.setting("theme").unwrap(), None);
+
+        database.set_setting("theme", "dark").unwrap();
+        database.set_setting("theme", "light").unwrap();
+        assert_eq!(database.setting("theme").unwrap().as_deref(), Some("light"));
+
+        database.remove_setting("theme").unwrap();
+        assert_eq!(database.setting("theme").unwrap(), None);
+    }
+}
@@ -1,6 +1,5 @@
-use std::rc::Rc;
-use std::cell::RefCell;
-use rust_slint_timer::timer::{TimerLogic, TimerState};
+use std::time::Duration;
+use rust_slint_timer::timer::{MockClock, TimerLogic};
 
 #[cfg(test)]
 mod integration_tests {
@@ -9,7 +8,8 @@ mod integration_tests {
     #[test]
     fn test_timer_logic_integration() {
         // Test complete timer workflow
-        let mut timer = TimerLogic::with_time(0, 0, 3).unwrap();
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 3, clock.clone()).unwrap();
         
         // Test initial state
         assert!(!timer.is_running());
@@ -22,14 +22,17 @@ mod integration_tests {
         assert!(!timer.is_completed());
         
         // Test tick progression
+        clock.advance(Duration::from_secs(1));
         assert!(!timer.tick()); // 3 -> 2
         assert_eq!(timer.get_remaining_time_string(), "00:00:02");
         assert!(timer.is_running());
         
+        clock.advance(Duration::from_secs(1));
         assert!(!timer.tick()); // 2 -> 1
         assert_eq!(timer.get_remaining_time_string(), "00:00:01");
         assert!(timer.is_running());
         
+        clock.advance(Duration::from_secs(1));
         assert!(timer.tick()); // 1 -> 0 (completion)
         assert_eq!(timer.get_remaining_time_string(), "00:00:00");
         assert!(!timer.is_running());
@@ -46,7 +49,8 @@ mod integration_tests {
     fn test_callback_integration() {
         use std::sync::{Arc, Mutex};
         
-        let mut timer = TimerLogic::with_time(0, 0, 2).unwrap();
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 2, clock.clone()).unwrap();
         let callback_states = Arc::new(Mutex::new(Vec::new()));
         let callback_states_clone = callback_states.clone();
         
@@ -57,7 +61,9 @@ mod integration_tests {
         
         // Test complete workflow with callbacks
         timer.start_timer(); // Should trigger callback
+        clock.advance(Duration::from_secs(1));
         timer.tick(); // Should trigger callback
+        clock.advance(Duration::from_secs(1));
         timer.tick(); // Should trigger callback twice (tick + completion)
         
         let states = callback_states.lock().unwrap();
@@ -65,8 +71,8 @@ mod integration_tests {
         
         // Verify state progression
         assert!(states[0].is_running && !states[0].is_completed); // start
-        assert_eq!(states[1].remaining_seconds, 1); // first tick
-        assert_eq!(states[2].remaining_seconds, 0); // second tick
+        assert_eq!(states[1].remaining_seconds(), 1); // first tick
+        assert_eq!(states[2].remaining_seconds(), 0); // second tick
         assert!(!states[3].is_running && states[3].is_completed); // completion
     }
 
@@ -95,24 +101,35 @@ mod integration_tests {
 
     #[test]
     fn test_pause_resume_integration() {
-        let mut timer = TimerLogic::with_time(0, 0, 5).unwrap();
+        let clock = MockClock::new();
+        let mut timer = TimerLogic::with_time_and_clock(0, 0, 5, clock.clone()).unwrap();
         
-        // Start and tick once
+        // Start and let one second pass
         timer.start_timer();
+        clock.advance(Duration::from_secs(1));
         timer.tick();
         assert_eq!(timer.get_remaining_time_string(), "00:00:04");
         assert!(timer.is_running());
         
-        // Pause and verify tick doesn't progress
+        // Pause and verify time spent paused doesn't count down
         timer.pause_timer();
         assert!(!timer.is_running());
+        clock.advance(Duration::from_secs(30));
         timer.tick();
         assert_eq!(timer.get_remaining_time_string(), "00:00:04"); // Should not change
+        assert_eq!(timer.elapsed(), Duration::from_secs(1));
         
-        // Resume and verify tick works again
+        // Resume and verify the countdown continues
         timer.start_timer();
         assert!(timer.is_running());
+        clock.advance(Duration::from_secs(1));
         timer.tick();
         assert_eq!(timer.get_remaining_time_string(), "00:00:03");
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_module_paths_stay_public() {
+        let state = rust_slint_timer::timer::state::TimerState::new(0, 1, 0);
+        let timer = rust_slint_timer::timer::logic::TimerLogic::with_time(0, 1, 0).unwrap();
+        assert_eq!(timer.get_state(), &state);
+    }
+}